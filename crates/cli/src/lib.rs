@@ -1,12 +1,18 @@
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use pdf_engine::{default_engine, OpenSource, PdfEngine, ThumbnailSize};
+use image::Rgba;
+use pdf_engine::{
+    default_engine, merge_pdfs, validate_pdf, OpenSource, OutlineItem, PdfEngine, RgbaImage,
+};
 use serde::Serialize;
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::fs;
+use std::io::Cursor;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
+pub use pdf_engine::ThumbnailSize;
+
 #[derive(Debug, Parser)]
 #[command(name = "butterpaper-cli")]
 #[command(about = "ButterPaper CLI")]
@@ -26,6 +32,35 @@ enum Commands {
     Info {
         #[arg(value_name = "FILE")]
         file: PathBuf,
+        /// Also report the first page size in pixels at this resolution.
+        #[arg(long)]
+        dpi: Option<u32>,
+    },
+    /// Print the document outline (table of contents) as JSON.
+    Outline {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+        /// Only include entries up to this nesting depth (1 = top level).
+        #[arg(long)]
+        max_depth: Option<u32>,
+        /// Emit a flat list with a `depth` field instead of nested `children`.
+        #[arg(long)]
+        flat: bool,
+    },
+    /// Concatenate PDFs into a new file, in the order given.
+    Merge {
+        #[arg(value_name = "FILE", num_args = 2.., required = true)]
+        inputs: Vec<PathBuf>,
+        #[arg(long)]
+        output: PathBuf,
+    },
+    /// Check a PDF's structure and report problems as JSON.
+    Validate {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+        /// Exit with a failure status when any warning is reported.
+        #[arg(long)]
+        strict: bool,
     },
     /// Render a thumbnail PNG for a page.
     RenderThumb {
@@ -37,6 +72,25 @@ enum Commands {
         width: u32,
         #[arg(long, default_value_t = 320)]
         height: u32,
+        /// Destination PNG; must have a `.png` extension.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Render pages into a single grid PNG, each cell labeled with its page number.
+    ContactSheet {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+        #[arg(long, default_value_t = 4)]
+        columns: u32,
+        #[arg(long, default_value_t = 160)]
+        cell_width: u32,
+        #[arg(long, default_value_t = 200)]
+        cell_height: u32,
+        #[arg(long, default_value_t = 8)]
+        gap: u32,
+        /// Only include the first N pages.
+        #[arg(long)]
+        max_pages: Option<u32>,
         #[arg(long)]
         output: Option<PathBuf>,
     },
@@ -44,19 +98,74 @@ enum Commands {
     Version,
 }
 
+/// Most per-page sizes `info` lists; `distinct_sizes` still covers every page.
+const MAX_LISTED_PAGE_SIZES: usize = 100;
+
 #[derive(Debug, Serialize)]
 struct InfoOutput {
     path: String,
     page_count: u32,
     first_page_size_pt: Option<PageSizeOutput>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_page_size_px: Option<PixelSizeOutput>,
+    uniform: bool,
+    page_sizes: Vec<PageSizeOutput>,
+    distinct_sizes: Vec<DistinctSizeOutput>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 struct PageSizeOutput {
     width: f32,
     height: f32,
 }
 
+#[derive(Debug, Serialize)]
+struct DistinctSizeOutput {
+    width: f32,
+    height: f32,
+    count: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct PixelSizeOutput {
+    width: u32,
+    height: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct MergeOutput {
+    path: String,
+    page_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct ValidateOutput {
+    ok: bool,
+    page_count: u32,
+    warnings: Vec<ValidationWarningOutput>,
+}
+
+#[derive(Debug, Serialize)]
+struct ValidationWarningOutput {
+    code: &'static str,
+    page: Option<u32>,
+    message: String,
+}
+
+#[derive(Debug, Serialize)]
+struct OutlineNodeOutput {
+    title: String,
+    page: Option<u32>,
+    children: Vec<OutlineNodeOutput>,
+}
+
+#[derive(Debug, Serialize)]
+struct FlatOutlineOutput {
+    title: String,
+    page: Option<u32>,
+    depth: u32,
+}
+
 pub fn run<I, T>(args: I) -> Result<()>
 where
     I: IntoIterator<Item = T>,
@@ -66,10 +175,27 @@ where
 
     match cli.command {
         Commands::Open { file } => run_open(&file),
-        Commands::Info { file } => run_info(&file),
+        Commands::Info { file, dpi } => run_info(&file, dpi),
+        Commands::Outline { file, max_depth, flat } => run_outline(&file, max_depth, flat),
+        Commands::Merge { inputs, output } => run_merge(&inputs, &output),
+        Commands::Validate { file, strict } => run_validate(&file, strict),
         Commands::RenderThumb { file, page, width, height, output } => {
             run_render_thumb(&file, page, width, height, output.as_deref())
         }
+        Commands::ContactSheet {
+            file,
+            columns,
+            cell_width,
+            cell_height,
+            gap,
+            max_pages,
+            output,
+        } => run_contact_sheet(
+            &file,
+            ContactSheetLayout { columns, cell_width, cell_height, gap },
+            max_pages,
+            output.as_deref(),
+        ),
         Commands::Version => {
             println!("{}", env!("CARGO_PKG_VERSION"));
             Ok(())
@@ -99,26 +225,186 @@ fn run_open(file: &Path) -> Result<()> {
     Ok(())
 }
 
-fn run_info(file: &Path) -> Result<()> {
+fn run_info(file: &Path, dpi: Option<u32>) -> Result<()> {
+    ensure_pdf_exists(file)?;
+
+    if dpi == Some(0) {
+        anyhow::bail!("--dpi must be a positive number");
+    }
+
+    let mut engine = default_engine();
+    let handle = engine.open(OpenSource::from(file)).context("failed to open PDF")?;
+
+    let mut page_sizes: Vec<PageSizeOutput> = engine
+        .page_sizes(handle)?
+        .into_iter()
+        .map(|size| PageSizeOutput { width: size.width_pt, height: size.height_pt })
+        .collect();
+    let page_count = page_sizes.len() as u32;
+
+    let first_page_size_pt = page_sizes.first().copied();
+    let distinct_sizes = group_page_sizes(&page_sizes);
+    page_sizes.truncate(MAX_LISTED_PAGE_SIZES);
+
+    let first_page_size_px = dpi.and_then(|dpi| {
+        first_page_size_pt.as_ref().map(|size| PixelSizeOutput {
+            width: points_to_pixels(size.width, dpi),
+            height: points_to_pixels(size.height, dpi),
+        })
+    });
+
+    let payload = InfoOutput {
+        path: file.display().to_string(),
+        page_count,
+        first_page_size_pt,
+        first_page_size_px,
+        uniform: distinct_sizes.len() <= 1,
+        page_sizes,
+        distinct_sizes,
+    };
+
+    let json = serde_json::to_string_pretty(&payload)?;
+    println!("{json}");
+
+    engine.close(handle)?;
+
+    Ok(())
+}
+
+fn run_outline(file: &Path, max_depth: Option<u32>, flat: bool) -> Result<()> {
     ensure_pdf_exists(file)?;
 
+    if max_depth == Some(0) {
+        anyhow::bail!("--max-depth must be a positive number");
+    }
+
     let mut engine = default_engine();
     let handle = engine.open(OpenSource::from(file)).context("failed to open PDF")?;
+    let outline = engine.outline(handle).context("failed to read outline")?;
+    let max_depth = max_depth.unwrap_or(u32::MAX);
 
-    let page_count = engine.page_count(handle)?;
-    let first_page_size_pt = if page_count > 0 {
-        let size = engine.page_size(handle, 0)?;
-        Some(PageSizeOutput { width: size.width_pt, height: size.height_pt })
+    let json = if flat {
+        let mut entries = Vec::new();
+        flatten_outline(&outline, 1, max_depth, &mut entries);
+        serde_json::to_string_pretty(&entries)?
     } else {
-        None
+        serde_json::to_string_pretty(&nest_outline(&outline, 1, max_depth))?
     };
+    println!("{json}");
+
+    engine.close(handle)?;
+
+    Ok(())
+}
+
+/// Counts pages per size, in order of first appearance.
+fn group_page_sizes(page_sizes: &[PageSizeOutput]) -> Vec<DistinctSizeOutput> {
+    let mut groups: Vec<DistinctSizeOutput> = Vec::new();
+
+    for size in page_sizes {
+        match groups
+            .iter_mut()
+            .find(|group| group.width == size.width && group.height == size.height)
+        {
+            Some(group) => group.count += 1,
+            None => {
+                groups.push(DistinctSizeOutput { width: size.width, height: size.height, count: 1 })
+            }
+        }
+    }
+
+    groups
+}
+
+fn nest_outline(items: &[OutlineItem], depth: u32, max_depth: u32) -> Vec<OutlineNodeOutput> {
+    if depth > max_depth {
+        return Vec::new();
+    }
+
+    items
+        .iter()
+        .map(|item| OutlineNodeOutput {
+            title: item.title.clone(),
+            page: item.page_index.map(|index| index + 1),
+            children: nest_outline(&item.children, depth + 1, max_depth),
+        })
+        .collect()
+}
+
+fn flatten_outline(
+    items: &[OutlineItem],
+    depth: u32,
+    max_depth: u32,
+    entries: &mut Vec<FlatOutlineOutput>,
+) {
+    if depth > max_depth {
+        return;
+    }
+
+    for item in items {
+        entries.push(FlatOutlineOutput {
+            title: item.title.clone(),
+            page: item.page_index.map(|index| index + 1),
+            depth,
+        });
+        flatten_outline(&item.children, depth + 1, max_depth, entries);
+    }
+}
+
+fn run_merge(inputs: &[PathBuf], output: &Path) -> Result<()> {
+    let mut documents = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        ensure_pdf_exists(input)?;
+        documents
+            .push(fs::read(input).with_context(|| format!("failed to read {}", input.display()))?);
+    }
+
+    let merged = merge_pdfs(&documents).context("failed to merge PDFs")?;
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(output, &merged)
+        .with_context(|| format!("failed to write merged PDF to {}", output.display()))?;
 
-    let payload = InfoOutput { path: file.display().to_string(), page_count, first_page_size_pt };
+    let mut engine = default_engine();
+    let handle = engine.open(OpenSource::from(merged)).context("failed to open merged PDF")?;
+    let payload =
+        MergeOutput { path: output.display().to_string(), page_count: engine.page_count(handle)? };
+    engine.close(handle)?;
 
     let json = serde_json::to_string_pretty(&payload)?;
     println!("{json}");
 
-    engine.close(handle)?;
+    Ok(())
+}
+
+fn run_validate(file: &Path, strict: bool) -> Result<()> {
+    ensure_pdf_exists(file)?;
+
+    let bytes = fs::read(file).with_context(|| format!("failed to read {}", file.display()))?;
+    let report = validate_pdf(&bytes);
+
+    let payload = ValidateOutput {
+        ok: report.is_ok(),
+        page_count: report.page_count,
+        warnings: report
+            .warnings
+            .iter()
+            .map(|warning| ValidationWarningOutput {
+                code: warning.code.as_str(),
+                page: warning.page_index.map(|index| index + 1),
+                message: warning.message.clone(),
+            })
+            .collect(),
+    };
+
+    let json = serde_json::to_string_pretty(&payload)?;
+    println!("{json}");
+
+    if strict && !payload.ok {
+        anyhow::bail!("validation reported {} warning(s)", payload.warnings.len());
+    }
 
     Ok(())
 }
@@ -130,6 +416,43 @@ fn run_render_thumb(
     height: u32,
     output: Option<&Path>,
 ) -> Result<()> {
+    if let Some(output) = output {
+        let is_png = output
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("png"));
+        if !is_png {
+            anyhow::bail!("--output must be a .png file: {}", output.display());
+        }
+    }
+
+    let png = render_thumbnail_to_png_bytes(
+        file,
+        page,
+        ThumbnailSize { width_px: width, height_px: height },
+    )?;
+
+    let output =
+        output.map(ToOwned::to_owned).unwrap_or_else(|| default_thumbnail_output(file, page));
+
+    if let Some(parent) = output.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(&output, png)
+        .with_context(|| format!("failed to write image to {}", output.display()))?;
+
+    println!("{}", output.display());
+
+    Ok(())
+}
+
+/// Renders a thumbnail of `page` (1-based) and returns it PNG-encoded, without touching disk.
+pub fn render_thumbnail_to_png_bytes(
+    file: &Path,
+    page: u32,
+    size: ThumbnailSize,
+) -> Result<Vec<u8>> {
     ensure_pdf_exists(file)?;
 
     if page == 0 {
@@ -139,29 +462,179 @@ fn run_render_thumb(
     let mut engine = default_engine();
     let handle = engine.open(OpenSource::from(file)).context("failed to open PDF")?;
 
-    let page_index = page - 1;
-    let image = engine
-        .render_thumbnail(handle, page_index, ThumbnailSize { width_px: width, height_px: height })
-        .context("failed to render thumbnail")?;
+    let image =
+        engine.render_thumbnail(handle, page - 1, size).context("failed to render thumbnail")?;
+
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+        .context("failed to encode thumbnail as PNG")?;
+
+    engine.close(handle)?;
+
+    Ok(png)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ContactSheetLayout {
+    columns: u32,
+    cell_width: u32,
+    cell_height: u32,
+    gap: u32,
+}
+
+impl ContactSheetLayout {
+    /// Sheet size for `cells` cells; `gap` separates cells and surrounds the grid.
+    ///
+    /// Fails when either dimension doesn't fit in a `u32`.
+    fn sheet_size(&self, cells: u32) -> Result<(u32, u32)> {
+        let columns = self.columns.min(cells).max(1);
+        let rows = cells.div_ceil(self.columns).max(1);
+
+        let extent = |count: u32, cell: u32| {
+            count.checked_mul(cell)?.checked_add(count.checked_add(1)?.checked_mul(self.gap)?)
+        };
+
+        match (extent(columns, self.cell_width), extent(rows, self.cell_height)) {
+            (Some(width), Some(height)) => Ok((width, height)),
+            _ => anyhow::bail!(
+                "contact sheet for {cells} page(s) is too large; reduce --cell-width, \
+                 --cell-height, --gap or --max-pages"
+            ),
+        }
+    }
+
+    fn cell_origin(&self, index: u32) -> (u32, u32) {
+        let column = index % self.columns;
+        let row = index / self.columns;
+
+        (
+            self.gap + column * (self.cell_width + self.gap),
+            self.gap + row * (self.cell_height + self.gap),
+        )
+    }
+}
+
+const CONTACT_SHEET_BACKGROUND: Rgba<u8> = Rgba([224, 224, 224, 255]);
+const LABEL_SCALE: u32 = 2;
+const LABEL_PADDING: u32 = 2;
+
+/// 3x5 bitmaps for the digits 0-9, one row per entry, high bit on the left.
+const DIGIT_GLYPHS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+fn run_contact_sheet(
+    file: &Path,
+    layout: ContactSheetLayout,
+    max_pages: Option<u32>,
+    output: Option<&Path>,
+) -> Result<()> {
+    ensure_pdf_exists(file)?;
+
+    if layout.columns == 0 {
+        anyhow::bail!("--columns must be a positive number");
+    }
+    if layout.cell_width == 0 || layout.cell_height == 0 {
+        anyhow::bail!("--cell-width and --cell-height must be positive numbers");
+    }
+    if max_pages == Some(0) {
+        anyhow::bail!("--max-pages must be a positive number");
+    }
+
+    let mut engine = default_engine();
+    let handle = engine.open(OpenSource::from(file)).context("failed to open PDF")?;
+
+    let page_count = engine.page_count(handle)?.min(max_pages.unwrap_or(u32::MAX));
+    let (width, height) = layout.sheet_size(page_count)?;
+    let mut sheet = RgbaImage::from_pixel(width, height, CONTACT_SHEET_BACKGROUND);
+
+    let cell_size = ThumbnailSize { width_px: layout.cell_width, height_px: layout.cell_height };
+    for page_index in 0..page_count {
+        let thumbnail = engine
+            .render_thumbnail(handle, page_index, cell_size)
+            .with_context(|| format!("failed to render page {}", page_index + 1))?;
+
+        let (x, y) = layout.cell_origin(page_index);
+        image::imageops::overlay(&mut sheet, &thumbnail, i64::from(x), i64::from(y));
+        draw_page_label(&mut sheet, x, y + layout.cell_height, page_index + 1);
+    }
+
+    engine.close(handle)?;
 
     let output =
-        output.map(ToOwned::to_owned).unwrap_or_else(|| default_thumbnail_output(file, page));
+        output.map(ToOwned::to_owned).unwrap_or_else(|| default_contact_sheet_output(file));
 
     if let Some(parent) = output.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    image
-        .save(&output)
+    sheet
+        .save_with_format(&output, image::ImageFormat::Png)
         .with_context(|| format!("failed to write image to {}", output.display()))?;
 
     println!("{}", output.display());
 
-    engine.close(handle)?;
-
     Ok(())
 }
 
+/// Draws `number` in black on a white box whose bottom-left corner is at `(x, bottom)`.
+fn draw_page_label(sheet: &mut RgbaImage, x: u32, bottom: u32, number: u32) {
+    let digits: Vec<usize> =
+        number.to_string().bytes().map(|digit| usize::from(digit - b'0')).collect();
+    let glyph_width = 3 * LABEL_SCALE;
+    let glyph_height = 5 * LABEL_SCALE;
+    let box_width =
+        digits.len() as u32 * (glyph_width + LABEL_SCALE) - LABEL_SCALE + 2 * LABEL_PADDING;
+    let box_height = glyph_height + 2 * LABEL_PADDING;
+    let top = bottom.saturating_sub(box_height);
+
+    let mut put = |px: u32, py: u32, color: Rgba<u8>| {
+        if px < sheet.width() && py < sheet.height() {
+            sheet.put_pixel(px, py, color);
+        }
+    };
+
+    for py in top..bottom {
+        for px in x..x + box_width {
+            put(px, py, Rgba([255, 255, 255, 255]));
+        }
+    }
+
+    for (position, digit) in digits.into_iter().enumerate() {
+        let glyph_x = x + LABEL_PADDING + position as u32 * (glyph_width + LABEL_SCALE);
+        for (row, bits) in DIGIT_GLYPHS[digit].iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) == 0 {
+                    continue;
+                }
+                for dy in 0..LABEL_SCALE {
+                    for dx in 0..LABEL_SCALE {
+                        put(
+                            glyph_x + column * LABEL_SCALE + dx,
+                            top + LABEL_PADDING + row as u32 * LABEL_SCALE + dy,
+                            Rgba([0, 0, 0, 255]),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn points_to_pixels(points: f32, dpi: u32) -> u32 {
+    (points / 72.0 * dpi as f32).round() as u32
+}
+
 fn ensure_pdf_exists(path: &Path) -> Result<()> {
     if !path.exists() {
         anyhow::bail!("file does not exist: {}", path.display());
@@ -171,9 +644,38 @@ fn ensure_pdf_exists(path: &Path) -> Result<()> {
         anyhow::bail!("path is not a file: {}", path.display());
     }
 
+    if let Some(allowed_dirs) = std::env::var_os("BUTTERPAPER_ALLOWED_DIRS") {
+        ensure_path_allowed(path, &allowed_dirs)?;
+    }
+
     Ok(())
 }
 
+/// Checks that `path` resolves to somewhere under one of the `allowed_dirs` roots
+/// (separated like `PATH`). Both sides are canonicalized so `..` and symlinks can't escape.
+fn ensure_path_allowed(path: &Path, allowed_dirs: &OsStr) -> Result<()> {
+    let canonical = path
+        .canonicalize()
+        .with_context(|| format!("failed to resolve path: {}", path.display()))?;
+
+    let allowed = std::env::split_paths(allowed_dirs)
+        .filter(|root| !root.as_os_str().is_empty())
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| canonical.starts_with(root));
+
+    if !allowed {
+        anyhow::bail!("path is outside BUTTERPAPER_ALLOWED_DIRS: {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn default_contact_sheet_output(file: &Path) -> PathBuf {
+    let stem = file.file_stem().and_then(|name| name.to_str()).unwrap_or("contact-sheet");
+
+    file.with_file_name(format!("{stem}-contact-sheet.png"))
+}
+
 fn default_thumbnail_output(file: &Path, page: u32) -> PathBuf {
     let stem = file.file_stem().and_then(|name| name.to_str()).unwrap_or("thumbnail");
 
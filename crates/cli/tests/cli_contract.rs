@@ -1,4 +1,5 @@
 use assert_cmd::cargo::cargo_bin_cmd;
+use butterpaper_cli::ThumbnailSize;
 use predicates::prelude::*;
 use serde_json::Value;
 use std::path::PathBuf;
@@ -7,22 +8,237 @@ fn fixture(name: &str) -> PathBuf {
     PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures").join(name)
 }
 
+fn run_json(subcommand: &str, file: &str, args: &[&str]) -> Value {
+    let output = cargo_bin_cmd!("butterpaper-cli")
+        .arg(subcommand)
+        .arg(fixture(file))
+        .args(args)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    serde_json::from_slice(&output).expect("stdout should contain valid json")
+}
+
 #[test]
 fn info_emits_stable_json_contract() {
-    let output = cargo_bin_cmd!("butterpaper-cli")
+    let mut value = run_json("info", "small.pdf", &[]);
+    value["path"] = Value::String("<FIXTURE>".to_owned());
+
+    insta::assert_json_snapshot!("cli_info_small_pdf", value);
+}
+
+#[test]
+fn info_dpi_72_matches_point_dimensions() {
+    let value = run_json("info", "small.pdf", &["--dpi", "72"]);
+
+    assert_eq!(value["first_page_size_pt"]["width"], 612.0);
+    assert_eq!(value["first_page_size_pt"]["height"], 792.0);
+    assert_eq!(value["first_page_size_px"]["width"], 612);
+    assert_eq!(value["first_page_size_px"]["height"], 792);
+}
+
+#[test]
+fn info_dpi_144_doubles_point_dimensions() {
+    let value = run_json("info", "small.pdf", &["--dpi", "144"]);
+
+    assert_eq!(value["first_page_size_pt"]["width"], 612.0);
+    assert_eq!(value["first_page_size_px"]["width"], 1224);
+    assert_eq!(value["first_page_size_px"]["height"], 1584);
+}
+
+#[test]
+fn info_omits_pixel_size_without_dpi() {
+    let value = run_json("info", "small.pdf", &[]);
+
+    assert!(value.get("first_page_size_px").is_none());
+}
+
+#[test]
+fn info_rejects_zero_dpi() {
+    cargo_bin_cmd!("butterpaper-cli")
         .arg("info")
         .arg(fixture("small.pdf"))
+        .arg("--dpi")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--dpi must be a positive number"));
+}
+
+#[test]
+fn info_reports_mixed_page_sizes_as_distinct_groups() {
+    let value = run_json("info", "mixed-sizes.pdf", &[]);
+
+    assert_eq!(value["uniform"], false);
+    assert_eq!(value["page_sizes"].as_array().map(Vec::len), Some(3));
+    assert_eq!(value["page_sizes"][1]["width"], 792.0);
+    assert_eq!(value["page_sizes"][1]["height"], 1224.0);
+
+    let distinct = value["distinct_sizes"].as_array().expect("distinct_sizes should be an array");
+    assert_eq!(distinct.len(), 2);
+    assert_eq!(distinct[0]["width"], 612.0);
+    assert_eq!(distinct[0]["count"], 2);
+    assert_eq!(distinct[1]["height"], 1224.0);
+    assert_eq!(distinct[1]["count"], 1);
+}
+
+#[test]
+fn info_reports_uniform_page_sizes() {
+    let value = run_json("info", "large.pdf", &[]);
+
+    assert_eq!(value["uniform"], true);
+    assert_eq!(value["page_sizes"].as_array().map(Vec::len), Some(20));
+    assert_eq!(value["distinct_sizes"][0]["count"], 20);
+}
+
+#[test]
+fn outline_nested_preserves_hierarchy() {
+    let value = run_json("outline", "outline.pdf", &[]);
+
+    assert_eq!(value[0]["title"], "Chapter 1");
+    assert_eq!(value[0]["page"], 1);
+    assert_eq!(value[0]["children"][0]["title"], "Section 1.1");
+    assert_eq!(value[0]["children"][0]["page"], 2);
+    assert_eq!(value[0]["children"][1]["children"][0]["title"], "Details");
+    assert_eq!(value[1]["title"], "Chapter 2");
+    assert_eq!(value[1]["page"], 4);
+    assert_eq!(value.as_array().map(Vec::len), Some(2));
+}
+
+#[test]
+fn outline_flat_reports_depths() {
+    let value = run_json("outline", "outline.pdf", &["--flat"]);
+
+    let entries: Vec<(String, u64)> = value
+        .as_array()
+        .expect("flat outline should be an array")
+        .iter()
+        .map(|entry| {
+            (entry["title"].as_str().unwrap().to_owned(), entry["depth"].as_u64().unwrap())
+        })
+        .collect();
+
+    assert_eq!(
+        entries,
+        vec![
+            ("Chapter 1".to_owned(), 1),
+            ("Section 1.1".to_owned(), 2),
+            ("Section 1.2".to_owned(), 2),
+            ("Details".to_owned(), 3),
+            ("Chapter 2".to_owned(), 1),
+        ]
+    );
+}
+
+#[test]
+fn outline_max_depth_limits_nesting() {
+    let value = run_json("outline", "outline.pdf", &["--max-depth", "1"]);
+    assert_eq!(value[0]["children"], Value::Array(Vec::new()));
+
+    let value = run_json("outline", "outline.pdf", &["--flat", "--max-depth", "2"]);
+    assert_eq!(value.as_array().map(Vec::len), Some(4));
+}
+
+#[test]
+fn outline_prints_empty_array_without_bookmarks() {
+    let value = run_json("outline", "small.pdf", &[]);
+
+    assert_eq!(value, Value::Array(Vec::new()));
+}
+
+#[test]
+fn merge_concatenates_inputs_and_reports_page_count() {
+    let temp = tempfile::tempdir().expect("temp dir should be created");
+    let output_path = temp.path().join("merged.pdf");
+
+    let output = cargo_bin_cmd!("butterpaper-cli")
+        .arg("merge")
+        .arg(fixture("small.pdf"))
+        .arg(fixture("mixed-sizes.pdf"))
+        .arg("--output")
+        .arg(&output_path)
         .assert()
         .success()
         .get_output()
         .stdout
         .clone();
 
-    let mut value: Value =
-        serde_json::from_slice(&output).expect("stdout should contain valid json");
-    value["path"] = Value::String("<FIXTURE>".to_owned());
+    let value: Value = serde_json::from_slice(&output).expect("stdout should contain valid json");
+    assert_eq!(value["page_count"], 4);
 
-    insta::assert_json_snapshot!("cli_info_small_pdf", value);
+    let output = cargo_bin_cmd!("butterpaper-cli")
+        .arg("info")
+        .arg(&output_path)
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let info: Value = serde_json::from_slice(&output).expect("stdout should contain valid json");
+    assert_eq!(info["page_count"], 4);
+    assert_eq!(info["first_page_size_pt"]["width"], 612.0);
+    assert_eq!(info["first_page_size_pt"]["height"], 792.0);
+    assert_eq!(info["page_sizes"][2]["width"], 792.0);
+    assert_eq!(info["page_sizes"][2]["height"], 1224.0);
+}
+
+#[test]
+fn merge_requires_at_least_two_inputs() {
+    let temp = tempfile::tempdir().expect("temp dir should be created");
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("merge")
+        .arg(fixture("small.pdf"))
+        .arg("--output")
+        .arg(temp.path().join("merged.pdf"))
+        .assert()
+        .failure();
+}
+
+#[test]
+fn validate_reports_clean_fixture_as_ok() {
+    let value = run_json("validate", "medium.pdf", &[]);
+
+    assert_eq!(value["ok"], true);
+    assert_eq!(value["page_count"], 5);
+    assert_eq!(value["warnings"], Value::Array(Vec::new()));
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("validate")
+        .arg("--strict")
+        .arg(fixture("medium.pdf"))
+        .assert()
+        .success();
+}
+
+#[test]
+fn validate_warns_about_missing_media_box() {
+    let value = run_json("validate", "missing-mediabox.pdf", &[]);
+
+    assert_eq!(value["ok"], false);
+    assert_eq!(value["page_count"], 2);
+    assert_eq!(value["warnings"][0]["code"], "MISSING_MEDIABOX");
+    assert_eq!(value["warnings"][0]["page"], 2);
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("validate")
+        .arg("--strict")
+        .arg(fixture("missing-mediabox.pdf"))
+        .assert()
+        .failure()
+        .stdout(predicate::str::contains("MISSING_MEDIABOX"));
+}
+
+#[test]
+fn validate_reports_encrypted_file() {
+    let value = run_json("validate", "encrypted-marker.pdf", &[]);
+
+    assert_eq!(value["ok"], false);
+    assert_eq!(value["warnings"][0]["code"], "ENCRYPTED");
 }
 
 #[test]
@@ -36,6 +252,42 @@ fn open_supports_dry_run_for_tests() {
         .stdout(predicate::str::contains("open:"));
 }
 
+#[test]
+fn open_allows_paths_inside_allowed_dirs() {
+    let temp = tempfile::tempdir().expect("temp dir should be created");
+    let allowed = temp.path().join("allowed");
+    std::fs::create_dir_all(&allowed).expect("allowed dir should be created");
+    std::fs::copy(fixture("small.pdf"), allowed.join("small.pdf")).expect("fixture should copy");
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("open")
+        .arg(allowed.join("small.pdf"))
+        .env("BUTTERPAPER_TEST_NO_SPAWN", "1")
+        .env("BUTTERPAPER_ALLOWED_DIRS", &allowed)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("open:"));
+}
+
+#[test]
+fn open_rejects_paths_outside_allowed_dirs() {
+    let temp = tempfile::tempdir().expect("temp dir should be created");
+    let allowed = temp.path().join("allowed");
+    let outside = temp.path().join("outside");
+    std::fs::create_dir_all(&allowed).expect("allowed dir should be created");
+    std::fs::create_dir_all(&outside).expect("outside dir should be created");
+    std::fs::copy(fixture("small.pdf"), outside.join("small.pdf")).expect("fixture should copy");
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("open")
+        .arg(allowed.join("../outside/small.pdf"))
+        .env("BUTTERPAPER_TEST_NO_SPAWN", "1")
+        .env("BUTTERPAPER_ALLOWED_DIRS", &allowed)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("outside BUTTERPAPER_ALLOWED_DIRS"));
+}
+
 #[test]
 fn render_thumb_writes_png_file() {
     let temp = tempfile::tempdir().expect("temp dir should be created");
@@ -62,6 +314,78 @@ fn render_thumb_writes_png_file() {
     assert!(image.height() > 0);
 }
 
+#[test]
+fn render_thumb_rejects_non_png_output() {
+    let temp = tempfile::tempdir().expect("temp dir should be created");
+    let output_path = temp.path().join("thumb.jpg");
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("render-thumb")
+        .arg(fixture("small.pdf"))
+        .arg("--output")
+        .arg(&output_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--output must be a .png file"));
+
+    assert!(!output_path.exists());
+}
+
+#[test]
+fn render_thumbnail_to_png_bytes_returns_png_of_requested_size() {
+    let png = butterpaper_cli::render_thumbnail_to_png_bytes(
+        &fixture("medium.pdf"),
+        2,
+        ThumbnailSize { width_px: 120, height_px: 90 },
+    )
+    .expect("thumbnail should render");
+
+    assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+    let image = image::load_from_memory(&png).expect("bytes should decode as an image");
+    assert_eq!((image.width(), image.height()), (120, 90));
+}
+
+#[test]
+fn contact_sheet_lays_out_pages_in_a_grid() {
+    let temp = tempfile::tempdir().expect("temp dir should be created");
+    let output_path = temp.path().join("sheet.png");
+
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("contact-sheet")
+        .arg(fixture("outline.pdf"))
+        .args(["--columns", "2", "--cell-width", "100", "--cell-height", "120", "--gap", "10"])
+        .arg("--output")
+        .arg(&output_path)
+        .assert()
+        .success();
+
+    let image = image::open(&output_path).expect("contact sheet should be readable image");
+    assert_eq!((image.width(), image.height()), (2 * 100 + 3 * 10, 2 * 120 + 3 * 10));
+}
+
+#[test]
+fn contact_sheet_rejects_zero_columns() {
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("contact-sheet")
+        .arg(fixture("outline.pdf"))
+        .args(["--columns", "0"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--columns must be a positive number"));
+}
+
+#[test]
+fn contact_sheet_rejects_sizes_that_overflow() {
+    cargo_bin_cmd!("butterpaper-cli")
+        .arg("contact-sheet")
+        .arg(fixture("outline.pdf"))
+        .args(["--cell-width", "4294967295"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("is too large"));
+}
+
 #[test]
 fn info_fails_for_missing_file() {
     cargo_bin_cmd!("butterpaper-cli")
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ViewMode {
@@ -27,11 +28,55 @@ pub struct ReaderState {
     pub view_mode: ViewMode,
     pub zoom_mode: ZoomMode,
     pub zoom_percent: u16,
+    #[serde(default)]
+    pub crop_box: Option<CropRect>,
 }
 
 impl Default for ReaderState {
     fn default() -> Self {
-        Self { view_mode: ViewMode::Continuous, zoom_mode: ZoomMode::FitPage, zoom_percent: 100 }
+        Self {
+            view_mode: ViewMode::Continuous,
+            zoom_mode: ZoomMode::FitPage,
+            zoom_percent: 100,
+            crop_box: None,
+        }
+    }
+}
+
+impl ReaderState {
+    /// Page size to lay out with, honoring the crop box when one is set.
+    pub fn effective_page_size(&self, full: PageSize) -> PageSize {
+        match self.crop_box {
+            Some(crop) => crop.clamped_to(full).size(),
+            None => full,
+        }
+    }
+}
+
+/// View-only crop in page points, measured from the page's top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CropRect {
+    pub x_pt: u32,
+    pub y_pt: u32,
+    pub width_pt: u32,
+    pub height_pt: u32,
+}
+
+impl CropRect {
+    pub fn size(&self) -> PageSize {
+        PageSize { width_pt: self.width_pt, height_pt: self.height_pt }
+    }
+
+    pub fn clamped_to(&self, page: PageSize) -> Self {
+        let x_pt = self.x_pt.min(page.width_pt);
+        let y_pt = self.y_pt.min(page.height_pt);
+
+        Self {
+            x_pt,
+            y_pt,
+            width_pt: self.width_pt.min(page.width_pt - x_pt),
+            height_pt: self.height_pt.min(page.height_pt - y_pt),
+        }
     }
 }
 
@@ -77,16 +122,65 @@ pub struct TabState {
     pub current_page: u32,
 }
 
+/// How welcome tabs are presented when the session creates them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WelcomeConfig {
+    pub title: String,
+}
+
+impl Default for WelcomeConfig {
+    fn default() -> Self {
+        Self { title: "Welcome".to_owned() }
+    }
+}
+
 impl TabState {
     pub fn new_welcome(id: TabId) -> Self {
+        Self::new_welcome_with(id, &WelcomeConfig::default())
+    }
+
+    pub fn new_welcome_with(id: TabId, config: &WelcomeConfig) -> Self {
         Self {
             id,
-            title: "Welcome".to_owned(),
+            title: config.title.clone(),
             content: TabContent::Welcome,
             reader: ReaderState::default(),
             current_page: 1,
         }
     }
+
+    /// Returns `title` shortened to at most `max_chars` characters for the tab strip. Long titles
+    /// lose their middle so the start and the file extension stay readable; `title` itself keeps
+    /// the full value for tooltips.
+    pub fn display_title(&self, max_chars: usize) -> String {
+        truncate_middle(&self.title, max_chars)
+    }
+}
+
+const TITLE_ELLIPSIS: &str = "...";
+
+fn truncate_middle(title: &str, max_chars: usize) -> String {
+    let chars: Vec<char> = title.chars().collect();
+    if chars.len() <= max_chars {
+        return title.to_owned();
+    }
+
+    let ellipsis_len = TITLE_ELLIPSIS.chars().count();
+    if max_chars <= ellipsis_len {
+        return chars[..max_chars].iter().collect();
+    }
+
+    let extension_len = match title.rfind('.') {
+        Some(0) | None => 0,
+        Some(index) => title[index..].chars().count(),
+    };
+    let budget = max_chars - ellipsis_len;
+    let tail_len = budget.div_ceil(2).max(extension_len).min(budget);
+    let head_len = budget - tail_len;
+
+    let head: String = chars[..head_len].iter().collect();
+    let tail: String = chars[chars.len() - tail_len..].iter().collect();
+    format!("{head}{TITLE_ELLIPSIS}{tail}")
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -94,11 +188,75 @@ pub struct Preferences {
     pub prefer_tabs: bool,
     pub show_tab_bar: bool,
     pub allow_window_merge: bool,
+    /// Most tabs a session keeps open; opening past it closes the least recently activated tab.
+    #[serde(default)]
+    pub max_tabs: Option<u32>,
 }
 
 impl Default for Preferences {
     fn default() -> Self {
-        Self { prefer_tabs: true, show_tab_bar: true, allow_window_merge: true }
+        Self { prefer_tabs: true, show_tab_bar: true, allow_window_merge: true, max_tabs: None }
+    }
+}
+
+pub const DEFAULT_RECENT_FILES_MAX: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentEntry {
+    pub path: PathBuf,
+    pub pinned: bool,
+    /// Seconds since the Unix epoch.
+    pub last_opened: u64,
+}
+
+/// Recently opened files, most recent first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentFiles {
+    pub entries: Vec<RecentEntry>,
+}
+
+impl RecentFiles {
+    /// Records `path` as opened at `opened_at`, moving an existing entry to the front and
+    /// keeping its pin.
+    pub fn add(&mut self, path: PathBuf, opened_at: u64) {
+        let pinned = match self.entries.iter().position(|entry| entry.path == path) {
+            Some(index) => self.entries.remove(index).pinned,
+            None => false,
+        };
+
+        self.entries.insert(0, RecentEntry { path, pinned, last_opened: opened_at });
+    }
+
+    pub fn pin(&mut self, path: &Path) -> bool {
+        self.set_pinned(path, true)
+    }
+
+    pub fn unpin(&mut self, path: &Path) -> bool {
+        self.set_pinned(path, false)
+    }
+
+    /// Drops the oldest unpinned entries until at most `max` remain. Pinned entries are never
+    /// dropped, so the list can stay above `max` when more than `max` entries are pinned.
+    pub fn trim(&mut self, max: usize) {
+        let mut excess = self.entries.len().saturating_sub(max);
+        let mut index = self.entries.len();
+
+        while excess > 0 && index > 0 {
+            index -= 1;
+            if !self.entries[index].pinned {
+                self.entries.remove(index);
+                excess -= 1;
+            }
+        }
+    }
+
+    fn set_pinned(&mut self, path: &Path, pinned: bool) -> bool {
+        let Some(entry) = self.entries.iter_mut().find(|entry| entry.path == path) else {
+            return false;
+        };
+
+        entry.pinned = pinned;
+        true
     }
 }
 
@@ -108,26 +266,35 @@ pub struct SessionState {
     pub active_tab: Option<TabId>,
     pub documents: BTreeMap<DocumentId, DocumentState>,
     pub preferences: Preferences,
+    #[serde(default)]
+    pub welcome: WelcomeConfig,
+    /// Tab ids in activation order, least recent first.
+    #[serde(default)]
+    tab_recency: Vec<TabId>,
     next_document_id: u64,
     next_tab_id: u64,
 }
 
 impl Default for SessionState {
     fn default() -> Self {
-        let welcome = TabState::new_welcome(TabId(1));
+        Self::with_welcome(WelcomeConfig::default())
+    }
+}
 
+impl SessionState {
+    pub fn with_welcome(welcome: WelcomeConfig) -> Self {
         Self {
-            tabs: vec![welcome],
+            tabs: vec![TabState::new_welcome_with(TabId(1), &welcome)],
             active_tab: Some(TabId(1)),
             documents: BTreeMap::new(),
             preferences: Preferences::default(),
+            welcome,
+            tab_recency: Vec::new(),
             next_document_id: 1,
             next_tab_id: 1,
         }
     }
-}
 
-impl SessionState {
     pub fn active_tab_mut(&mut self) -> Option<&mut TabState> {
         let active = self.active_tab?;
         self.tabs.iter_mut().find(|tab| tab.id == active)
@@ -160,6 +327,34 @@ impl SessionState {
     pub fn is_welcome_only(&self) -> bool {
         matches!(self.tabs.as_slice(), [tab] if matches!(tab.content, TabContent::Welcome))
     }
+
+    /// Like `TabState::display_title`, but prefixes the parent directory name when another
+    /// document tab shares the same title so same-named files can be told apart.
+    pub fn tab_display_title(&self, tab_id: TabId, max_chars: usize) -> Option<String> {
+        let tab = self.tabs.iter().find(|tab| tab.id == tab_id)?;
+        let TabContent::Document { document_id } = tab.content else {
+            return Some(tab.display_title(max_chars));
+        };
+
+        let shares_title = self.tabs.iter().any(|other| {
+            other.id != tab.id
+                && other.title == tab.title
+                && matches!(other.content, TabContent::Document { .. })
+        });
+        let parent = self
+            .documents
+            .get(&document_id)
+            .and_then(|document| document.path.parent())
+            .and_then(Path::file_name);
+
+        match parent {
+            Some(parent) if shares_title => Some(truncate_middle(
+                &format!("{}/{}", parent.to_string_lossy(), tab.title),
+                max_chars,
+            )),
+            _ => Some(tab.display_title(max_chars)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -167,10 +362,14 @@ pub enum SessionAction {
     OpenDocument { path: PathBuf, title: String, page_count: u32, first_page_size: PageSize },
     NewWelcomeTab,
     CloseTab { tab_id: TabId },
+    CloseAllTabs,
+    CloseOtherTabs { keep: TabId },
     ActivateTab { tab_id: TabId },
+    MoveTab { tab_id: TabId, to_index: usize },
     SetViewMode { tab_id: TabId, mode: ViewMode },
     SetZoomMode { tab_id: TabId, mode: ZoomMode },
     SetZoomPercent { tab_id: TabId, zoom_percent: u16 },
+    SetCropBox { tab_id: TabId, rect: Option<CropRect> },
     SetCurrentPage { tab_id: TabId, page: u32 },
     NextPage { tab_id: TabId },
     PreviousPage { tab_id: TabId },
@@ -187,6 +386,60 @@ pub fn apply_zoom_action(state: &mut ReaderState, action: ZoomAction) {
     }
 }
 
+pub const DEFAULT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Decides when a changed session should be written out. Performs no I/O
+/// itself: the caller saves when `tick` reports a save is due, then calls
+/// `mark_saved`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionAutosaver {
+    pub interval: Duration,
+    dirty: bool,
+    save_requested: bool,
+    last_saved: Instant,
+}
+
+impl SessionAutosaver {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self { interval, dirty: false, save_requested: false, last_saved: now }
+    }
+
+    /// Applies `action` to `state` and marks the session dirty.
+    pub fn apply(&mut self, state: &mut SessionState, action: SessionAction) {
+        apply_session_action(state, action);
+        self.mark_dirty();
+    }
+
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Returns true once when the session is dirty and `interval` has passed
+    /// since the last save; it stays quiet until `mark_saved` is called.
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if !self.dirty || self.save_requested {
+            return false;
+        }
+
+        if now.saturating_duration_since(self.last_saved) < self.interval {
+            return false;
+        }
+
+        self.save_requested = true;
+        true
+    }
+
+    pub fn mark_saved(&mut self, now: Instant) {
+        self.dirty = false;
+        self.save_requested = false;
+        self.last_saved = now;
+    }
+}
+
 pub fn apply_session_action(state: &mut SessionState, action: SessionAction) {
     match action {
         SessionAction::OpenDocument { path, title, page_count, first_page_size } => {
@@ -206,7 +459,9 @@ pub fn apply_session_action(state: &mut SessionState, action: SessionAction) {
                     welcome.content = TabContent::Document { document_id };
                     welcome.current_page = 1;
                 }
-                state.active_tab = state.tabs.first().map(|tab| tab.id);
+                if let Some(tab_id) = state.tabs.first().map(|tab| tab.id) {
+                    activate_tab(state, tab_id);
+                }
             } else {
                 let tab_id = state.new_tab_id();
                 state.tabs.push(TabState {
@@ -216,13 +471,14 @@ pub fn apply_session_action(state: &mut SessionState, action: SessionAction) {
                     reader: ReaderState::default(),
                     current_page: 1,
                 });
-                state.active_tab = Some(tab_id);
+                activate_tab(state, tab_id);
+                enforce_max_tabs(state, tab_id);
             }
         }
         SessionAction::NewWelcomeTab => {
             let tab_id = state.new_tab_id();
-            state.tabs.push(TabState::new_welcome(tab_id));
-            state.active_tab = Some(tab_id);
+            state.tabs.push(TabState::new_welcome_with(tab_id, &state.welcome));
+            activate_tab(state, tab_id);
         }
         SessionAction::CloseTab { tab_id } => {
             let Some(index) = state.tabs.iter().position(|tab| tab.id == tab_id) else {
@@ -232,33 +488,38 @@ pub fn apply_session_action(state: &mut SessionState, action: SessionAction) {
             state.tabs.remove(index);
 
             if state.tabs.is_empty() {
-                state.tabs.push(TabState::new_welcome(TabId(1)));
-                state.active_tab = Some(TabId(1));
-                state.documents.clear();
-                state.next_document_id = 1;
-                state.next_tab_id = 1;
+                reset_to_welcome(state);
                 return;
             }
 
             let fallback_index = index.saturating_sub(1).min(state.tabs.len() - 1);
-            state.active_tab = Some(state.tabs[fallback_index].id);
-
-            let referenced_documents: BTreeMap<DocumentId, ()> = state
-                .tabs
-                .iter()
-                .filter_map(|tab| match tab.content {
-                    TabContent::Document { document_id } => Some((document_id, ())),
-                    TabContent::Welcome => None,
-                })
-                .collect();
-
-            state.documents.retain(|id, _| referenced_documents.contains_key(id));
+            activate_tab(state, state.tabs[fallback_index].id);
+            retain_referenced_documents(state);
+        }
+        SessionAction::CloseAllTabs => reset_to_welcome(state),
+        SessionAction::CloseOtherTabs { keep } => {
+            if !state.tabs.iter().any(|tab| tab.id == keep) {
+                return;
+            }
+
+            state.tabs.retain(|tab| tab.id == keep);
+            activate_tab(state, keep);
+            retain_referenced_documents(state);
         }
         SessionAction::ActivateTab { tab_id } => {
             if state.tabs.iter().any(|tab| tab.id == tab_id) {
-                state.active_tab = Some(tab_id);
+                activate_tab(state, tab_id);
             }
         }
+        SessionAction::MoveTab { tab_id, to_index } => {
+            let Some(from_index) = state.tabs.iter().position(|tab| tab.id == tab_id) else {
+                return;
+            };
+
+            let tab = state.tabs.remove(from_index);
+            let to_index = to_index.min(state.tabs.len());
+            state.tabs.insert(to_index, tab);
+        }
         SessionAction::SetViewMode { tab_id, mode } => {
             if let Some(tab) = state.tabs.iter_mut().find(|tab| tab.id == tab_id) {
                 tab.reader.view_mode = mode;
@@ -275,6 +536,11 @@ pub fn apply_session_action(state: &mut SessionState, action: SessionAction) {
                 tab.reader.zoom_percent = zoom_percent.clamp(10, 1600);
             }
         }
+        SessionAction::SetCropBox { tab_id, rect } => {
+            if let Some(tab) = state.tabs.iter_mut().find(|tab| tab.id == tab_id) {
+                tab.reader.crop_box = rect;
+            }
+        }
         SessionAction::SetCurrentPage { tab_id, page } => {
             if let Some(index) = state.tabs.iter().position(|tab| tab.id == tab_id) {
                 let page_count = tab_page_count_by_index(state, index).unwrap_or(1);
@@ -297,6 +563,65 @@ pub fn apply_session_action(state: &mut SessionState, action: SessionAction) {
     }
 }
 
+fn reset_to_welcome(state: &mut SessionState) {
+    state.tabs = vec![TabState::new_welcome_with(TabId(1), &state.welcome)];
+    state.active_tab = Some(TabId(1));
+    state.tab_recency.clear();
+    state.documents.clear();
+    state.next_document_id = 1;
+    state.next_tab_id = 1;
+}
+
+fn activate_tab(state: &mut SessionState, tab_id: TabId) {
+    let SessionState { tabs, tab_recency, .. } = state;
+    tab_recency.retain(|id| *id != tab_id && tabs.iter().any(|tab| tab.id == *id));
+    tab_recency.push(tab_id);
+    state.active_tab = Some(tab_id);
+}
+
+/// Closes least recently activated tabs, never `opened`, until the tab count fits
+/// `Preferences::max_tabs`. Tabs that were never activated count as the oldest.
+fn enforce_max_tabs(state: &mut SessionState, opened: TabId) {
+    let Some(max_tabs) = state.preferences.max_tabs else {
+        return;
+    };
+
+    let max_tabs = (max_tabs as usize).max(1);
+    let mut evicted = false;
+    while state.tabs.len() > max_tabs {
+        let Some(lru) = state
+            .tabs
+            .iter()
+            .filter(|tab| tab.id != opened)
+            .min_by_key(|tab| state.tab_recency.iter().position(|id| *id == tab.id))
+            .map(|tab| tab.id)
+        else {
+            break;
+        };
+
+        state.tabs.retain(|tab| tab.id != lru);
+        state.tab_recency.retain(|id| *id != lru);
+        evicted = true;
+    }
+
+    if evicted {
+        retain_referenced_documents(state);
+    }
+}
+
+fn retain_referenced_documents(state: &mut SessionState) {
+    let referenced_documents: BTreeMap<DocumentId, ()> = state
+        .tabs
+        .iter()
+        .filter_map(|tab| match tab.content {
+            TabContent::Document { document_id } => Some((document_id, ())),
+            TabContent::Welcome => None,
+        })
+        .collect();
+
+    state.documents.retain(|id, _| referenced_documents.contains_key(id));
+}
+
 fn tab_page_count_by_index(state: &SessionState, tab_index: usize) -> Option<u32> {
     let tab = state.tabs.get(tab_index)?;
     let TabContent::Document { document_id } = tab.content else {
@@ -316,6 +641,7 @@ mod tests {
             view_mode: ViewMode::Continuous,
             zoom_mode: ZoomMode::FitPage,
             zoom_percent: 143,
+            crop_box: None,
         };
         apply_zoom_action(&mut continuous, ZoomAction::ActualSize100);
         assert_eq!(continuous.zoom_mode, ZoomMode::Percent);
@@ -325,6 +651,7 @@ mod tests {
             view_mode: ViewMode::SinglePage,
             zoom_mode: ZoomMode::FitWidth,
             zoom_percent: 66,
+            crop_box: None,
         };
         apply_zoom_action(&mut single, ZoomAction::ActualSize100);
         assert_eq!(single.zoom_mode, ZoomMode::Percent);
@@ -436,4 +763,306 @@ mod tests {
         let tab = state.active_tab().expect("active tab expected");
         assert_eq!(tab.current_page, 3);
     }
+
+    fn recent_paths(recent: &RecentFiles) -> Vec<&str> {
+        recent.entries.iter().map(|entry| entry.path.to_str().expect("utf-8 path")).collect()
+    }
+
+    #[test]
+    fn adding_existing_recent_file_moves_it_to_front() {
+        let mut recent = RecentFiles::default();
+        recent.add(PathBuf::from("/tmp/a.pdf"), 1);
+        recent.add(PathBuf::from("/tmp/b.pdf"), 2);
+        recent.pin(Path::new("/tmp/a.pdf"));
+        recent.add(PathBuf::from("/tmp/a.pdf"), 3);
+
+        assert_eq!(recent_paths(&recent), vec!["/tmp/a.pdf", "/tmp/b.pdf"]);
+        assert_eq!(recent.entries[0].last_opened, 3);
+        assert!(recent.entries[0].pinned);
+    }
+
+    #[test]
+    fn trimming_recent_files_keeps_pinned_entries() {
+        let mut recent = RecentFiles::default();
+        for (i, name) in ["a", "b", "c", "d"].iter().enumerate() {
+            recent.add(PathBuf::from(format!("/tmp/{name}.pdf")), i as u64);
+        }
+        assert!(recent.pin(Path::new("/tmp/a.pdf")));
+
+        recent.trim(2);
+        assert_eq!(recent_paths(&recent), vec!["/tmp/d.pdf", "/tmp/a.pdf"]);
+
+        recent.trim(0);
+        assert_eq!(recent_paths(&recent), vec!["/tmp/a.pdf"]);
+
+        assert!(recent.unpin(Path::new("/tmp/a.pdf")));
+        recent.trim(0);
+        assert!(recent.entries.is_empty());
+        assert!(!recent.pin(Path::new("/tmp/missing.pdf")));
+    }
+
+    #[test]
+    fn crop_box_changes_effective_page_size_until_cleared() {
+        let mut state = SessionState::default();
+        apply_session_action(
+            &mut state,
+            SessionAction::OpenDocument {
+                path: PathBuf::from("/tmp/test.pdf"),
+                title: "test.pdf".to_owned(),
+                page_count: 1,
+                first_page_size: PageSize::default(),
+            },
+        );
+
+        let tab_id = state.active_tab.expect("active tab expected");
+        let full = PageSize::default();
+        let crop = CropRect { x_pt: 36, y_pt: 36, width_pt: 540, height_pt: 700 };
+
+        apply_session_action(&mut state, SessionAction::SetCropBox { tab_id, rect: Some(crop) });
+        let reader = state.active_tab().expect("active tab expected").reader;
+        assert_eq!(reader.effective_page_size(full), PageSize { width_pt: 540, height_pt: 700 });
+
+        apply_session_action(&mut state, SessionAction::SetCropBox { tab_id, rect: None });
+        let reader = state.active_tab().expect("active tab expected").reader;
+        assert_eq!(reader.effective_page_size(full), full);
+    }
+
+    #[test]
+    fn crop_box_is_clamped_to_page_bounds() {
+        let reader = ReaderState {
+            crop_box: Some(CropRect { x_pt: 600, y_pt: 100, width_pt: 100, height_pt: 2000 }),
+            ..ReaderState::default()
+        };
+
+        let size = reader.effective_page_size(PageSize::default());
+        assert_eq!(size, PageSize { width_pt: 12, height_pt: 692 });
+    }
+
+    #[test]
+    fn reader_state_crop_box_serde_round_trip() {
+        let reader = ReaderState {
+            crop_box: Some(CropRect { x_pt: 10, y_pt: 20, width_pt: 300, height_pt: 400 }),
+            ..ReaderState::default()
+        };
+
+        let json = serde_json::to_string(&reader).expect("serialize reader state");
+        let parsed: ReaderState = serde_json::from_str(&json).expect("deserialize reader state");
+        assert_eq!(parsed, reader);
+
+        let legacy = r#"{"view_mode":"Continuous","zoom_mode":"FitPage","zoom_percent":100}"#;
+        let parsed: ReaderState = serde_json::from_str(legacy).expect("deserialize legacy state");
+        assert_eq!(parsed.crop_box, None);
+    }
+
+    fn open_documents(state: &mut SessionState, count: usize) {
+        for i in 0..count {
+            apply_session_action(
+                state,
+                SessionAction::OpenDocument {
+                    path: PathBuf::from(format!("/tmp/test-{i}.pdf")),
+                    title: format!("test-{i}.pdf"),
+                    page_count: 2,
+                    first_page_size: PageSize::default(),
+                },
+            );
+        }
+    }
+
+    fn open_document_at(state: &mut SessionState, path: &str) {
+        let path = PathBuf::from(path);
+        let title = path.file_name().expect("path has a file name").to_string_lossy().into_owned();
+        apply_session_action(
+            state,
+            SessionAction::OpenDocument {
+                path,
+                title,
+                page_count: 1,
+                first_page_size: PageSize::default(),
+            },
+        );
+    }
+
+    #[test]
+    fn display_title_truncates_middle_and_keeps_extension() {
+        let mut tab = TabState::new_welcome(TabId(1));
+        tab.title = "my_long_quarterly_report.pdf".to_owned();
+
+        assert_eq!(tab.display_title(20), "my_long_...eport.pdf");
+        assert_eq!(tab.display_title(10), "my_....pdf");
+        assert_eq!(tab.display_title(64), "my_long_quarterly_report.pdf");
+        assert_eq!(tab.title, "my_long_quarterly_report.pdf");
+    }
+
+    #[test]
+    fn same_named_documents_show_parent_directory() {
+        let mut state = SessionState::default();
+        open_document_at(&mut state, "/work/q1/report.pdf");
+        open_document_at(&mut state, "/work/q2/report.pdf");
+        open_document_at(&mut state, "/work/notes.pdf");
+
+        let titles: Vec<String> = state
+            .tabs
+            .iter()
+            .map(|tab| state.tab_display_title(tab.id, 32).expect("tab exists"))
+            .collect();
+        assert_eq!(titles, vec!["q1/report.pdf", "q2/report.pdf", "notes.pdf"]);
+        assert_eq!(state.tab_display_title(TabId(999), 32), None);
+    }
+
+    fn tab_titles(state: &SessionState) -> Vec<&str> {
+        state.tabs.iter().map(|tab| tab.title.as_str()).collect()
+    }
+
+    #[test]
+    fn opening_past_max_tabs_evicts_least_recently_activated_tab() {
+        let mut state = SessionState::default();
+        state.preferences.max_tabs = Some(3);
+        open_documents(&mut state, 3);
+
+        let first = state.tabs[0].id;
+        apply_session_action(&mut state, SessionAction::ActivateTab { tab_id: first });
+
+        apply_session_action(
+            &mut state,
+            SessionAction::OpenDocument {
+                path: PathBuf::from("/tmp/extra.pdf"),
+                title: "extra.pdf".to_owned(),
+                page_count: 1,
+                first_page_size: PageSize::default(),
+            },
+        );
+
+        assert_eq!(tab_titles(&state), vec!["test-0.pdf", "test-2.pdf", "extra.pdf"]);
+        assert_eq!(state.active_document().map(|doc| doc.title.as_str()), Some("extra.pdf"));
+        assert_eq!(state.documents.len(), 3);
+        assert!(state.documents.values().all(|doc| doc.title != "test-1.pdf"));
+    }
+
+    #[test]
+    fn max_tabs_never_closes_the_tab_being_opened() {
+        let mut state = SessionState::default();
+        state.preferences.max_tabs = Some(0);
+        open_documents(&mut state, 3);
+
+        assert_eq!(tab_titles(&state), vec!["test-2.pdf"]);
+        assert_eq!(state.documents.len(), 1);
+    }
+
+    #[test]
+    fn no_max_tabs_keeps_every_tab() {
+        let mut state = SessionState::default();
+        assert_eq!(state.preferences.max_tabs, None);
+        open_documents(&mut state, 12);
+
+        assert_eq!(state.tabs.len(), 12);
+        assert_eq!(state.documents.len(), 12);
+    }
+
+    #[test]
+    fn close_all_tabs_resets_to_single_welcome_tab() {
+        let mut state = SessionState::default();
+        open_documents(&mut state, 3);
+
+        apply_session_action(&mut state, SessionAction::CloseAllTabs);
+
+        assert_eq!(state, SessionState::default());
+        assert!(state.documents.is_empty());
+        assert!(state.is_welcome_only());
+    }
+
+    #[test]
+    fn close_other_tabs_keeps_only_the_given_tab() {
+        let mut state = SessionState::default();
+        open_documents(&mut state, 3);
+        let keep = state.tabs[1].id;
+        let TabContent::Document { document_id } = state.tabs[1].content else {
+            panic!("expected a document tab");
+        };
+
+        apply_session_action(&mut state, SessionAction::CloseOtherTabs { keep });
+
+        assert_eq!(state.tabs.len(), 1);
+        assert_eq!(state.tabs[0].id, keep);
+        assert_eq!(state.active_tab, Some(keep));
+        assert_eq!(state.documents.keys().copied().collect::<Vec<_>>(), vec![document_id]);
+    }
+
+    #[test]
+    fn close_other_tabs_ignores_unknown_tab() {
+        let mut state = SessionState::default();
+        open_documents(&mut state, 2);
+        let before = state.clone();
+
+        apply_session_action(&mut state, SessionAction::CloseOtherTabs { keep: TabId(999) });
+
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn custom_welcome_title_applies_to_new_and_reset_tabs() {
+        let mut state = SessionState::with_welcome(WelcomeConfig { title: "Start".to_owned() });
+        assert_eq!(state.tabs[0].title, "Start");
+
+        apply_session_action(&mut state, SessionAction::NewWelcomeTab);
+        assert_eq!(state.tabs[1].title, "Start");
+
+        open_documents(&mut state, 1);
+        for tab_id in state.tabs.iter().map(|tab| tab.id).collect::<Vec<_>>() {
+            apply_session_action(&mut state, SessionAction::CloseTab { tab_id });
+        }
+
+        assert!(state.is_welcome_only());
+        assert_eq!(state.tabs[0].title, "Start");
+
+        open_documents(&mut state, 2);
+        apply_session_action(&mut state, SessionAction::CloseAllTabs);
+        assert_eq!(state.tabs[0].title, "Start");
+    }
+
+    #[test]
+    fn move_tab_reorders_and_keeps_active_tab() {
+        let mut state = SessionState::default();
+        open_documents(&mut state, 3);
+        let ids: Vec<TabId> = state.tabs.iter().map(|tab| tab.id).collect();
+        apply_session_action(&mut state, SessionAction::ActivateTab { tab_id: ids[1] });
+
+        apply_session_action(&mut state, SessionAction::MoveTab { tab_id: ids[2], to_index: 0 });
+        assert_eq!(
+            state.tabs.iter().map(|tab| tab.id).collect::<Vec<_>>(),
+            vec![ids[2], ids[0], ids[1]]
+        );
+        assert_eq!(state.active_tab, Some(ids[1]));
+
+        apply_session_action(&mut state, SessionAction::MoveTab { tab_id: ids[2], to_index: 99 });
+        assert_eq!(
+            state.tabs.iter().map(|tab| tab.id).collect::<Vec<_>>(),
+            vec![ids[0], ids[1], ids[2]]
+        );
+        assert_eq!(state.active_tab, Some(ids[1]));
+    }
+
+    #[test]
+    fn autosaver_reports_due_once_after_interval() {
+        let start = Instant::now();
+        let interval = Duration::from_secs(30);
+        let mut autosaver = SessionAutosaver::new(interval, start);
+        let mut state = SessionState::default();
+
+        assert!(!autosaver.tick(start + interval));
+
+        autosaver.apply(&mut state, SessionAction::NewWelcomeTab);
+        assert!(autosaver.is_dirty());
+        assert!(!autosaver.tick(start + interval / 2));
+        assert!(autosaver.tick(start + interval));
+        assert!(!autosaver.tick(start + interval * 2));
+
+        let saved_at = start + interval * 2;
+        autosaver.mark_saved(saved_at);
+        assert!(!autosaver.is_dirty());
+        assert!(!autosaver.tick(saved_at + interval));
+
+        autosaver.apply(&mut state, SessionAction::CloseAllTabs);
+        assert!(!autosaver.tick(saved_at + interval / 2));
+        assert!(autosaver.tick(saved_at + interval));
+    }
 }
@@ -25,7 +25,7 @@ use crate::ui::TypographyExt;
 use crate::ui_preferences::save_ui_preferences_from_app;
 use crate::viewport::{PdfViewport, PerfSnapshot, ViewMode, ZoomMode};
 use crate::workspace::{load_preferences, TabPreferences};
-use crate::{current_theme, ui, Theme, ThumbnailClusterWidthPx};
+use crate::{current_theme, ui, PageGapPx, Theme, ThumbnailClusterWidthPx};
 use crate::{
     CheckForUpdates, CloseTab, CloseWindow, FirstPage, FitPage, FitWidth, LastPage, NextPage,
     NextTab, Open, PrevPage, PrevTab, ResetZoom, ZoomIn, ZoomOut,
@@ -409,6 +409,9 @@ impl PdfEditor {
         let sidebar =
             cx.new(move |cx| ThumbnailSidebar::new_with_preview_cache(sidebar_preview.clone(), cx));
 
+        let page_gap = cx.try_global::<PageGapPx>().copied().unwrap_or_default().0;
+        viewport.update(cx, |vp, cx| vp.set_page_gap(page_gap, cx));
+
         // Set up page change callback from viewport to sidebar
         let sidebar_weak = sidebar.downgrade();
         viewport.update(cx, |vp, _cx| {
@@ -1890,7 +1893,7 @@ impl PdfEditor {
             )
         };
 
-        let base_bg = if selected { theme.element_selected } else { theme.surface };
+        let base_bg = if selected { theme.palette().selection } else { theme.surface };
         let border = ui::color::subtle_border(theme.border);
         let icon_color = if enabled { theme.text_muted } else { theme.text_muted };
 
@@ -1920,7 +1923,7 @@ impl PdfEditor {
                     .rounded(ui::sizes::RADIUS_SM)
                     .when(enabled, {
                         let hover = theme.element_hover;
-                        let active = theme.element_selected;
+                        let active = theme.palette().selection;
                         move |d| {
                             d.cursor_pointer()
                                 .hover(move |s| s.bg(hover))
@@ -2501,7 +2504,7 @@ impl Render for PdfEditor {
                                     .justify_center()
                                     .px(ui::sizes::SPACE_1)
                                     .rounded(ui::sizes::RADIUS_SM)
-                                    .when(zoom_input_selected, |d| d.bg(theme.element_selected))
+                                    .when(zoom_input_selected, |d| d.bg(theme.palette().selection))
                                     .child(self.zoom_input_text.clone()),
                             )
                             .on_click(move |_, window, cx| {
@@ -2631,7 +2634,7 @@ impl Render for PdfEditor {
                                 .justify_center()
                                 .px(ui::sizes::SPACE_1)
                                 .rounded(ui::sizes::RADIUS_SM)
-                                .when(page_input_selected, |d| d.bg(theme.element_selected))
+                                .when(page_input_selected, |d| d.bg(theme.palette().selection))
                                 .child(self.page_input_text.clone()),
                         )
                         .on_click(move |_, window, cx| {
@@ -254,7 +254,7 @@ pub fn render_update_banner(
         .justify_between()
         .px(crate::ui::sizes::SPACE_4)
         .py(crate::ui::sizes::SPACE_2)
-        .bg(theme.element_selected)
+        .bg(theme.palette().selection)
         .border_b_1()
         .border_color(theme.border)
         .child(gpui::div().text_ui_body().text_color(theme.text).child(format!(
@@ -293,7 +293,7 @@ pub fn render_update_check_banner(
 ) -> impl gpui::IntoElement {
     let (bg, border) = match banner {
         UpdateCheckBanner::Error { .. } => (theme.danger_bg, theme.danger_border),
-        _ => (theme.element_selected, theme.border),
+        _ => (theme.palette().selection, theme.border),
     };
 
     let (message, show_dismiss) = match banner {
@@ -2,7 +2,7 @@
 
 #![allow(dead_code)]
 
-use butterpaper_render::RenderQuality;
+use butterpaper_render::{rgba_to_bgra_in_place, RenderQuality};
 use image::{ImageBuffer, Rgba};
 use serde::Serialize;
 use smallvec::SmallVec;
@@ -22,6 +22,102 @@ pub enum MemoryPressureState {
     Critical,
 }
 
+/// RSS-to-budget ratios at which each memory pressure band begins.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryPressureThresholds {
+    warm: f64,
+    hot: f64,
+    critical: f64,
+    hysteresis: f64,
+}
+
+impl MemoryPressureThresholds {
+    /// Returns `None` unless `0 < warm < hot < critical` and the hysteresis is
+    /// non-negative and narrower than the smallest band.
+    pub fn new(warm: f64, hot: f64, critical: f64, hysteresis: f64) -> Option<Self> {
+        let ordered = 0.0 < warm && warm < hot && hot < critical && critical.is_finite();
+        let narrowest_band = warm.min(hot - warm).min(critical - hot);
+        if !ordered || !(0.0..narrowest_band).contains(&hysteresis) {
+            return None;
+        }
+
+        Some(Self { warm, hot, critical, hysteresis })
+    }
+
+    /// Parses `warm,hot,critical,hysteresis`, e.g. `0.7,0.82,0.92,0.03`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let values = value
+            .split(',')
+            .map(|part| part.trim().parse::<f64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let [warm, hot, critical, hysteresis] = values.as_slice() else {
+            return None;
+        };
+        Self::new(*warm, *hot, *critical, *hysteresis)
+    }
+
+    /// Thresholds from `BUTTERPAPER_MEMORY_PRESSURE_THRESHOLDS`, or the defaults when it is
+    /// unset or invalid.
+    pub fn detect() -> Self {
+        std::env::var("BUTTERPAPER_MEMORY_PRESSURE_THRESHOLDS")
+            .ok()
+            .and_then(|value| Self::parse(&value))
+            .unwrap_or_default()
+    }
+
+    /// Band for `rss_ratio` ignoring the current state.
+    pub fn classify(&self, rss_ratio: f64) -> MemoryPressureState {
+        if rss_ratio >= self.critical {
+            MemoryPressureState::Critical
+        } else if rss_ratio >= self.hot {
+            MemoryPressureState::Hot
+        } else if rss_ratio >= self.warm {
+            MemoryPressureState::Warm
+        } else {
+            MemoryPressureState::Normal
+        }
+    }
+
+    /// Band to move to from `current`. Stepping down one band requires the ratio to
+    /// drop `hysteresis` below that band's threshold, which prevents flapping.
+    pub fn next_state(
+        &self,
+        current: MemoryPressureState,
+        rss_ratio: f64,
+        queue_hot: bool,
+    ) -> MemoryPressureState {
+        let mut target = self.classify(rss_ratio);
+        if queue_hot && matches!(target, MemoryPressureState::Normal) {
+            target = MemoryPressureState::Warm;
+        }
+
+        match (current, target) {
+            (MemoryPressureState::Critical, MemoryPressureState::Hot)
+                if rss_ratio > self.critical - self.hysteresis =>
+            {
+                MemoryPressureState::Critical
+            }
+            (MemoryPressureState::Hot, MemoryPressureState::Warm)
+                if rss_ratio > self.hot - self.hysteresis =>
+            {
+                MemoryPressureState::Hot
+            }
+            (MemoryPressureState::Warm, MemoryPressureState::Normal)
+                if rss_ratio > self.warm - self.hysteresis =>
+            {
+                MemoryPressureState::Warm
+            }
+            _ => target,
+        }
+    }
+}
+
+impl Default for MemoryPressureThresholds {
+    fn default() -> Self {
+        Self { warm: 0.70, hot: 0.82, critical: 0.92, hysteresis: 0.03 }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AdaptiveMemoryBudget {
     pub total_budget_bytes: u64,
@@ -378,9 +474,7 @@ pub fn create_render_image(
     height: u32,
 ) -> Option<Arc<gpui::RenderImage>> {
     let mut bgra_pixels = rgba_pixels;
-    for pixel in bgra_pixels.chunks_exact_mut(4) {
-        pixel.swap(0, 2);
-    }
+    rgba_to_bgra_in_place(&mut bgra_pixels);
 
     let buffer = ImageBuffer::<Rgba<u8>, Vec<u8>>::from_raw(width, height, bgra_pixels)?;
     let frame = image::Frame::new(buffer);
@@ -464,6 +558,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pressure_thresholds_classify_each_band() {
+        let thresholds = MemoryPressureThresholds::new(0.5, 0.7, 0.9, 0.02).unwrap();
+
+        assert_eq!(thresholds.classify(0.2), MemoryPressureState::Normal);
+        assert_eq!(thresholds.classify(0.5), MemoryPressureState::Warm);
+        assert_eq!(thresholds.classify(0.75), MemoryPressureState::Hot);
+        assert_eq!(thresholds.classify(0.95), MemoryPressureState::Critical);
+    }
+
+    #[test]
+    fn pressure_thresholds_reject_misordered_values() {
+        assert!(MemoryPressureThresholds::new(0.8, 0.7, 0.9, 0.02).is_none());
+        assert!(MemoryPressureThresholds::new(0.5, 0.9, 0.9, 0.02).is_none());
+        assert!(MemoryPressureThresholds::new(0.0, 0.7, 0.9, 0.02).is_none());
+        assert!(MemoryPressureThresholds::new(0.5, 0.7, 0.9, -0.01).is_none());
+        assert!(MemoryPressureThresholds::new(0.5, 0.55, 0.9, 0.1).is_none());
+        assert!(MemoryPressureThresholds::new(0.5, 0.7, f64::NAN, 0.02).is_none());
+    }
+
+    #[test]
+    fn pressure_thresholds_parse_from_comma_separated_values() {
+        assert_eq!(
+            MemoryPressureThresholds::parse("0.5, 0.7,0.9,0.02"),
+            MemoryPressureThresholds::new(0.5, 0.7, 0.9, 0.02)
+        );
+        assert!(MemoryPressureThresholds::parse("0.5,0.7,0.9").is_none());
+        assert!(MemoryPressureThresholds::parse("0.9,0.7,0.5,0.02").is_none());
+        assert!(MemoryPressureThresholds::parse("warm,0.7,0.9,0.02").is_none());
+    }
+
+    #[test]
+    fn pressure_thresholds_apply_hysteresis_when_stepping_down() {
+        let thresholds = MemoryPressureThresholds::default();
+
+        assert_eq!(
+            thresholds.next_state(MemoryPressureState::Hot, 0.81, false),
+            MemoryPressureState::Hot
+        );
+        assert_eq!(
+            thresholds.next_state(MemoryPressureState::Hot, 0.78, false),
+            MemoryPressureState::Warm
+        );
+        assert_eq!(
+            thresholds.next_state(MemoryPressureState::Normal, 0.1, true),
+            MemoryPressureState::Warm
+        );
+    }
+
     #[test]
     fn retain_recomputes_accounting() {
         let mut cache = ByteLruCache::new(256 * 1024);
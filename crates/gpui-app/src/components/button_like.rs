@@ -124,7 +124,7 @@ pub fn variant_colors(variant: ButtonLikeVariant, theme: &Theme) -> ButtonLikeCo
             text: theme.text,
             border: subtle_border(theme),
             hover: theme.element_hover,
-            active: theme.element_selected,
+            active: theme.palette().selection,
         },
         ButtonLikeVariant::Accent => ButtonLikeColors {
             background: theme.accent,
@@ -153,7 +153,7 @@ pub fn variant_colors(variant: ButtonLikeVariant, theme: &Theme) -> ButtonLikeCo
             text: theme.text,
             border: color::transparent(),
             hover: theme.element_hover,
-            active: theme.element_selected,
+            active: theme.palette().selection,
         },
         ButtonLikeVariant::Danger => ButtonLikeColors {
             background: theme.danger,
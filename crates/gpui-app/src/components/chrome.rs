@@ -23,14 +23,12 @@ pub fn chrome_control_shell(
     child: impl IntoElement,
 ) -> impl IntoElement {
     let control_size = chrome_control_size();
-    let base_background = if selected {
-        color::selected_surface(theme.element_selected)
-    } else {
-        theme.elevated_surface
-    };
+    let palette = theme.palette();
+    let base_background =
+        if selected { color::selected_surface(palette.selection) } else { palette.toolbar };
     let border = color::subtle_border(theme.border);
     let hover = if selected { base_background } else { theme.element_hover };
-    let active = theme.element_selected;
+    let active = palette.selection;
 
     div()
         .id(id.into())
@@ -104,14 +102,12 @@ where
     };
 
     let button_size = chrome_control_size();
-    let base_background = if selected {
-        color::selected_surface(theme.element_selected)
-    } else {
-        theme.elevated_surface
-    };
+    let palette = theme.palette();
+    let base_background =
+        if selected { color::selected_surface(palette.selection) } else { palette.toolbar };
     let border = color::subtle_border(theme.border);
     let hover = if selected { base_background } else { theme.element_hover };
-    let active = theme.element_selected;
+    let active = palette.selection;
 
     div()
         .id(id.into())
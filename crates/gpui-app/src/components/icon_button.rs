@@ -95,7 +95,7 @@ where
         text: text_color,
         border: subtle_border(theme),
         hover: theme.element_hover,
-        active: theme.element_selected,
+        active: theme.palette().selection,
     };
     if !enabled {
         colors.hover = colors.background;
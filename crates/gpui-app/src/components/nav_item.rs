@@ -32,7 +32,7 @@ where
     F: Fn(&ClickEvent, &mut Window, &mut gpui::App) + 'static,
 {
     let label: SharedString = label.into();
-    let element_selected = theme.element_selected;
+    let element_selected = theme.palette().selection;
     let element_hover = theme.element_hover;
     let (text, hover_text) = nav_item_text_colors(selected, theme);
 
@@ -64,7 +64,7 @@ where
     F: Fn(&ClickEvent, &mut Window, &mut gpui::App) + 'static,
 {
     let label: SharedString = label.into();
-    let element_selected = theme.element_selected;
+    let element_selected = theme.palette().selection;
     let element_hover = theme.element_hover;
     let (text, hover_text) = nav_item_text_colors(selected, theme);
 
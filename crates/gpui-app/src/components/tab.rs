@@ -42,10 +42,10 @@ where
     let subtle_border = color::subtle_border(theme.border);
     let inactive_bg = theme.elevated_surface;
     let hover_bg = theme.element_hover;
-    let active_bg = color::selected_surface(theme.element_selected);
-    let pressed_bg = theme.element_selected;
+    let active_bg = color::selected_surface(theme.palette().selection);
+    let pressed_bg = theme.palette().selection;
     let close_hover_bg = theme.element_hover;
-    let close_pressed_bg = theme.element_selected;
+    let close_pressed_bg = theme.palette().selection;
     let close_icon_color = if data.is_active { theme.text } else { theme.text_muted };
 
     div()
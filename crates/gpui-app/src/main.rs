@@ -51,6 +51,18 @@ impl Default for ThumbnailClusterWidthPx {
 
 impl Global for ThumbnailClusterWidthPx {}
 
+/// Gap between pages in continuous scroll, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageGapPx(pub f32);
+
+impl Default for PageGapPx {
+    fn default() -> Self {
+        Self(ui_preferences::PAGE_GAP_DEFAULT_PX)
+    }
+}
+
+impl Global for PageGapPx {}
+
 actions!(
     butterpaper,
     [
@@ -252,6 +264,7 @@ fn main() {
         cx.set_global(ui_preferences.appearance_mode);
         cx.set_global(ui_preferences.theme_settings);
         cx.set_global(ThumbnailClusterWidthPx(ui_preferences.thumbnail_cluster_width_px));
+        cx.set_global(PageGapPx(ui_preferences.page_gap_px));
         cx.set_global(ui_preferences.update_check_frequency);
         #[cfg(target_os = "macos")]
         macos::set_app_appearance(ui_preferences.appearance_mode);
@@ -1032,7 +1032,7 @@ impl Render for ThumbnailSidebar {
         }
         let text_muted = theme.text_muted;
         let element_hover = theme.element_hover;
-        let selected_bg = theme.element_selected;
+        let selected_bg = theme.palette().selection;
         let selected_border = color::subtle_border(theme.border);
         let scroll_handle = self.scrollbar.handle();
         let scrollbar = self.scrollbar.metrics();
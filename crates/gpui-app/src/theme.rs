@@ -143,6 +143,31 @@ pub struct ThemeColors {
     pub danger: Rgba,
     pub danger_bg: Rgba,
     pub danger_border: Rgba,
+    pub highlight_default: Rgba,
+    pub measurement: Rgba,
+}
+
+/// Named color roles for document chrome and markup.
+///
+/// Renderers read these roles instead of hard-coding colors so users can
+/// supply their own palette via `ThemeColors::custom`. The palette is a view
+/// of `ThemeColors` fields, so the two can't disagree.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThemePalette {
+    pub background: Rgba,
+    pub toolbar: Rgba,
+    pub selection: Rgba,
+    pub highlight_default: Rgba,
+    pub measurement: Rgba,
+}
+
+/// Default highlight fill and measurement stroke for light or dark themes
+fn markup_colors(is_dark: bool) -> (Rgba, Rgba) {
+    if is_dark {
+        (rgba(0xe5c07b, 0.35), rgba(0xff8c42, 1.0))
+    } else {
+        (rgba(0xfab005, 0.4), rgba(0xd9480f, 1.0))
+    }
 }
 
 /// Registry of available themes
@@ -265,6 +290,7 @@ impl ThemeDefinition {
             parse_color(style.error_background.as_deref()).unwrap_or(with_alpha(danger, 0.16));
         let danger_border =
             parse_color(style.error_border.as_deref()).unwrap_or(with_alpha(danger, 0.62));
+        let (highlight_default, measurement) = markup_colors(self.appearance == "dark");
 
         ThemeColors {
             background: parse_color(style.background.as_deref()).unwrap_or(rgba(0x282c33, 1.0)),
@@ -291,6 +317,8 @@ impl ThemeDefinition {
             danger,
             danger_bg,
             danger_border,
+            highlight_default,
+            measurement,
         }
     }
 }
@@ -298,6 +326,8 @@ impl ThemeDefinition {
 impl ThemeColors {
     /// Fallback light theme if loading fails
     pub fn fallback_light() -> Self {
+        let (highlight_default, measurement) = markup_colors(false);
+
         Self {
             background: rgba(0xdcdcdd, 1.0),
             surface: rgba(0xebebec, 1.0),
@@ -313,11 +343,15 @@ impl ThemeColors {
             danger: rgba(0xd36151, 1.0),
             danger_bg: rgba(0xfbdfd9, 1.0),
             danger_border: rgba(0xf6c6bd, 1.0),
+            highlight_default,
+            measurement,
         }
     }
 
     /// Fallback dark theme if loading fails
     pub fn fallback_dark() -> Self {
+        let (highlight_default, measurement) = markup_colors(true);
+
         Self {
             background: rgba(0x3b414d, 1.0),
             surface: rgba(0x2f343e, 1.0),
@@ -333,6 +367,40 @@ impl ThemeColors {
             danger: rgba(0xd07277, 1.0),
             danger_bg: rgba(0xd07277, 0.102),
             danger_border: rgba(0x4c2b2c, 1.0),
+            highlight_default,
+            measurement,
+        }
+    }
+
+    /// Build a theme around a user-supplied palette.
+    ///
+    /// Non-palette colors come from the light or dark fallback, picked by the
+    /// palette background's luminance.
+    pub fn custom(palette: ThemePalette) -> Self {
+        let base = if luminance(palette.background) < 0.5 {
+            Self::fallback_dark()
+        } else {
+            Self::fallback_light()
+        };
+
+        Self {
+            background: palette.background,
+            elevated_surface: palette.toolbar,
+            element_selected: palette.selection,
+            highlight_default: palette.highlight_default,
+            measurement: palette.measurement,
+            ..base
+        }
+    }
+
+    /// Named color roles for chrome and markup renderers, read from this theme's fields
+    pub fn palette(&self) -> ThemePalette {
+        ThemePalette {
+            background: self.background,
+            toolbar: self.elevated_surface,
+            selection: self.element_selected,
+            highlight_default: self.highlight_default,
+            measurement: self.measurement,
         }
     }
 }
@@ -371,6 +439,70 @@ fn with_alpha(color: Rgba, alpha_multiplier: f32) -> Rgba {
     Rgba { r: color.r, g: color.g, b: color.b, a: color.a * alpha_multiplier }
 }
 
+/// Relative luminance (Rec. 709 weights) of a color, ignoring alpha
+fn luminance(color: Rgba) -> f32 {
+    0.2126 * color.r + 0.7152 * color.g + 0.0722 * color.b
+}
+
+/// Fill for large surfaces such as the viewport gutter
+#[derive(Debug, Clone, PartialEq)]
+pub enum Background {
+    Solid(Rgba),
+    /// Gradient along the line `from` -> `to`, both given as fractions (0..=1)
+    /// of the filled area. Stops are `(position, color)` in ascending position.
+    LinearGradient {
+        from: (f32, f32),
+        to: (f32, f32),
+        stops: Vec<(f32, Rgba)>,
+    },
+}
+
+/// Color of `background` at point (`x`, `y`) of a `width` x `height` area
+pub fn sample_background(background: &Background, x: f32, y: f32, width: f32, height: f32) -> Rgba {
+    let (from, to, stops) = match background {
+        Background::Solid(color) => return *color,
+        Background::LinearGradient { from, to, stops } => (*from, *to, stops),
+    };
+
+    let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+        return Rgba { r: 0.0, g: 0.0, b: 0.0, a: 0.0 };
+    };
+
+    let px = if width > 0.0 { x / width } else { 0.0 };
+    let py = if height > 0.0 { y / height } else { 0.0 };
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq > 0.0 {
+        (((px - from.0) * dx + (py - from.1) * dy) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    if t <= first.0 {
+        return first.1;
+    }
+
+    for pair in stops.windows(2) {
+        let ((start, start_color), (end, end_color)) = (pair[0], pair[1]);
+        if t <= end {
+            let span = end - start;
+            let local = if span > 0.0 { (t - start) / span } else { 1.0 };
+            return lerp_rgba(start_color, end_color, local);
+        }
+    }
+
+    last.1
+}
+
+fn lerp_rgba(from: Rgba, to: Rgba, t: f32) -> Rgba {
+    Rgba {
+        r: from.r + (to.r - from.r) * t,
+        g: from.g + (to.g - from.g) * t,
+        b: from.b + (to.b - from.b) * t,
+        a: from.a + (to.a - from.a) * t,
+    }
+}
+
 // Backwards compatibility alias
 pub type Theme = ThemeColors;
 
@@ -384,7 +516,7 @@ pub fn theme_registry() -> &'static ThemeRegistry {
 
 #[cfg(test)]
 mod tests {
-    use super::{rgba, ThemeDefinition};
+    use super::{rgba, sample_background, Background, ThemeColors, ThemeDefinition, ThemePalette};
 
     fn parse_theme(style_json: &str) -> ThemeDefinition {
         let json = format!(r#"{{"name":"Test","appearance":"dark","style":{style_json}}}"#);
@@ -421,4 +553,83 @@ mod tests {
         assert_rgba_eq(colors.danger_bg, rgba(0x334455, 0.16));
         assert_rgba_eq(colors.danger_border, rgba(0x334455, 0.62));
     }
+
+    #[test]
+    fn solid_background_is_uniform() {
+        let color = rgba(0x336699, 1.0);
+        let background = Background::Solid(color);
+
+        for (x, y) in [(0.0, 0.0), (400.0, 300.0), (800.0, 600.0)] {
+            assert_rgba_eq(sample_background(&background, x, y, 800.0, 600.0), color);
+        }
+    }
+
+    #[test]
+    fn vertical_gradient_interpolates_stops() {
+        let background = Background::LinearGradient {
+            from: (0.0, 0.0),
+            to: (0.0, 1.0),
+            stops: vec![(0.0, rgba(0x000000, 1.0)), (1.0, rgba(0xffffff, 0.5))],
+        };
+
+        assert_rgba_eq(
+            sample_background(&background, 10.0, 0.0, 800.0, 600.0),
+            rgba(0x000000, 1.0),
+        );
+        assert_rgba_eq(
+            sample_background(&background, 700.0, 300.0, 800.0, 600.0),
+            gpui::Rgba { r: 0.5, g: 0.5, b: 0.5, a: 0.75 },
+        );
+        assert_rgba_eq(
+            sample_background(&background, 0.0, 600.0, 800.0, 600.0),
+            rgba(0xffffff, 0.5),
+        );
+    }
+
+    #[test]
+    fn dark_and_light_palettes_differ() {
+        let light = ThemeColors::fallback_light();
+        let dark = ThemeColors::fallback_dark();
+
+        assert_ne!(light.palette(), dark.palette());
+        assert_ne!(light.palette().highlight_default, dark.palette().highlight_default);
+        assert_ne!(light.palette().measurement, dark.palette().measurement);
+        assert_rgba_eq(light.palette().toolbar, light.elevated_surface);
+        assert_rgba_eq(dark.palette().selection, dark.element_selected);
+    }
+
+    #[test]
+    fn to_colors_derives_palette_from_theme_surfaces() {
+        let theme = parse_theme(
+            r##"{
+                "background":"#101010ff",
+                "editor.background":"#202020ff",
+                "element.selected":"#303030ff"
+            }"##,
+        );
+        let colors = theme.to_colors();
+        assert_rgba_eq(colors.palette().background, rgba(0x101010, 1.0));
+        assert_rgba_eq(colors.palette().toolbar, rgba(0x202020, 1.0));
+        assert_rgba_eq(colors.palette().selection, rgba(0x303030, 1.0));
+    }
+
+    #[test]
+    fn custom_palette_round_trips_values() {
+        let palette = ThemePalette {
+            background: rgba(0x0b1020, 1.0),
+            toolbar: rgba(0x141a2e, 1.0),
+            selection: rgba(0x2d6cdf, 0.5),
+            highlight_default: rgba(0x39ff14, 0.3),
+            measurement: rgba(0xff00aa, 1.0),
+        };
+
+        let theme = ThemeColors::custom(palette);
+        assert_eq!(theme.palette(), palette);
+        assert_rgba_eq(theme.background, palette.background);
+        assert_rgba_eq(theme.elevated_surface, palette.toolbar);
+        assert_rgba_eq(theme.element_selected, palette.selection);
+
+        // Dark palette backgrounds inherit the dark fallback for everything else.
+        assert_rgba_eq(theme.text, ThemeColors::fallback_dark().text);
+    }
 }
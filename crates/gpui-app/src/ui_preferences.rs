@@ -6,7 +6,7 @@ use gpui::App;
 
 use crate::theme::{AppearanceMode, ThemeSettings};
 use crate::app_update::UpdateCheckFrequency;
-use crate::ThumbnailClusterWidthPx;
+use crate::{PageGapPx, ThumbnailClusterWidthPx};
 
 pub const THUMBNAIL_CLUSTER_WIDTH_DEFAULT_PX: f32 =
     crate::ui::sizes::THUMBNAIL_CLUSTER_DEFAULT_WIDTH_PX;
@@ -24,6 +24,20 @@ pub fn clamp_thumbnail_cluster_width_pref(width_px: f32) -> f32 {
     width_px.clamp(THUMBNAIL_CLUSTER_WIDTH_MIN_PX, THUMBNAIL_CLUSTER_WIDTH_MAX_PX)
 }
 
+pub const PAGE_GAP_DEFAULT_PX: f32 = crate::viewport::PAGE_GAP;
+const PAGE_GAP_MAX_PX: f32 = 200.0;
+
+fn default_page_gap_px() -> f32 {
+    PAGE_GAP_DEFAULT_PX
+}
+
+pub fn clamp_page_gap_pref(gap_px: f32) -> f32 {
+    if !gap_px.is_finite() {
+        return PAGE_GAP_DEFAULT_PX;
+    }
+    gap_px.clamp(0.0, PAGE_GAP_MAX_PX)
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct UiPreferences {
     pub appearance_mode: AppearanceMode,
@@ -32,6 +46,8 @@ pub struct UiPreferences {
     pub thumbnail_cluster_width_px: f32,
     #[serde(default)]
     pub update_check_frequency: UpdateCheckFrequency,
+    #[serde(default = "default_page_gap_px")]
+    pub page_gap_px: f32,
 }
 
 impl Default for UiPreferences {
@@ -41,6 +57,7 @@ impl Default for UiPreferences {
             theme_settings: ThemeSettings::default(),
             thumbnail_cluster_width_px: THUMBNAIL_CLUSTER_WIDTH_DEFAULT_PX,
             update_check_frequency: UpdateCheckFrequency::default(),
+            page_gap_px: PAGE_GAP_DEFAULT_PX,
         }
     }
 }
@@ -64,6 +81,7 @@ pub fn load_ui_preferences() -> UiPreferences {
     };
     prefs.thumbnail_cluster_width_px =
         clamp_thumbnail_cluster_width_pref(prefs.thumbnail_cluster_width_px);
+    prefs.page_gap_px = clamp_page_gap_pref(prefs.page_gap_px);
     prefs
 }
 
@@ -98,6 +116,7 @@ pub fn collect_ui_preferences(cx: &App) -> UiPreferences {
             .try_global::<UpdateCheckFrequency>()
             .copied()
             .unwrap_or_default(),
+        page_gap_px: cx.try_global::<PageGapPx>().copied().unwrap_or_default().0,
     }
 }
 
@@ -108,8 +127,8 @@ pub fn save_ui_preferences_from_app(cx: &App) -> std::io::Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::UiPreferences;
-    use super::THUMBNAIL_CLUSTER_WIDTH_DEFAULT_PX;
+    use super::{clamp_page_gap_pref, UiPreferences};
+    use super::{PAGE_GAP_DEFAULT_PX, THUMBNAIL_CLUSTER_WIDTH_DEFAULT_PX};
     use crate::app_update::UpdateCheckFrequency;
     use crate::theme::AppearanceMode;
 
@@ -119,6 +138,7 @@ mod tests {
             appearance_mode: AppearanceMode::Dark,
             thumbnail_cluster_width_px: 320.0,
             update_check_frequency: UpdateCheckFrequency::Every6Hours,
+            page_gap_px: 8.0,
             ..UiPreferences::default()
         };
 
@@ -127,6 +147,7 @@ mod tests {
         assert_eq!(decoded.appearance_mode, AppearanceMode::Dark);
         assert_eq!(decoded.thumbnail_cluster_width_px, 320.0);
         assert_eq!(decoded.update_check_frequency, UpdateCheckFrequency::Every6Hours);
+        assert_eq!(decoded.page_gap_px, 8.0);
     }
 
     #[test]
@@ -142,5 +163,14 @@ mod tests {
         assert_eq!(decoded.theme_settings.light_theme, "One Light");
         assert_eq!(decoded.theme_settings.dark_theme, "One Dark");
         assert_eq!(decoded.thumbnail_cluster_width_px, THUMBNAIL_CLUSTER_WIDTH_DEFAULT_PX);
+        assert_eq!(decoded.page_gap_px, PAGE_GAP_DEFAULT_PX);
+    }
+
+    #[test]
+    fn page_gap_pref_is_clamped() {
+        assert_eq!(clamp_page_gap_pref(-4.0), 0.0);
+        assert_eq!(clamp_page_gap_pref(12.0), 12.0);
+        assert_eq!(clamp_page_gap_pref(10_000.0), 200.0);
+        assert_eq!(clamp_page_gap_pref(f32::NAN), PAGE_GAP_DEFAULT_PX);
     }
 }
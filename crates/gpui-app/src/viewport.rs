@@ -8,7 +8,7 @@
 
 use crate::cache::{
     create_render_image, AdaptiveMemoryBudget, ByteLruCache, CachedImage, MemoryPressureState,
-    RenderCacheKey,
+    MemoryPressureThresholds, RenderCacheKey,
 };
 use crate::components::{scrollbar_gutter, ScrollbarController};
 use crate::current_theme;
@@ -26,8 +26,8 @@ use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-/// Gap between pages in pixels
-const PAGE_GAP: f32 = 20.0;
+/// Default gap between pages in pixels
+pub const PAGE_GAP: f32 = 20.0;
 
 /// Buffer above/below viewport for pre-rendering
 const RENDER_BUFFER_NORMAL: f32 = 400.0;
@@ -47,10 +47,6 @@ const SCROLL_IDLE_DEBOUNCE: Duration = Duration::from_millis(80);
 const MAX_RENDER_EDGE_PX: u32 = 8192;
 const MAX_RENDER_MEGAPIXELS: u64 = 32;
 const MICRO_SCROLL_HYSTERESIS_PX: f32 = 24.0;
-const MEMORY_PRESSURE_NORMAL_MAX: f64 = 0.70;
-const MEMORY_PRESSURE_WARM_MAX: f64 = 0.82;
-const MEMORY_PRESSURE_HOT_MAX: f64 = 0.92;
-const MEMORY_PRESSURE_HYSTERESIS: f64 = 0.03;
 const IDLE_TRIM_COOLDOWN: Duration = Duration::from_millis(250);
 const IDLE_SETTLE_DEBOUNCE: Duration = Duration::from_millis(500);
 const MIN_ACTIVE_TARGET_BYTES: u64 = 512 * 1024 * 1024;
@@ -93,12 +89,12 @@ fn clamp_zoom(zoom: u32) -> u32 {
     zoom.clamp(MIN_ZOOM_PERCENT, MAX_ZOOM_PERCENT)
 }
 
-fn fit_width_percent(canvas_width: f32, page_width: f32) -> u32 {
+fn fit_width_percent(canvas_width: f32, page_width: f32, gap: f32) -> u32 {
     if canvas_width <= 0.0 || page_width <= 0.0 {
         return 100;
     }
 
-    let usable_width = (canvas_width - PAGE_GAP * 2.0).max(1.0);
+    let usable_width = (canvas_width - gap * 2.0).max(1.0);
     clamp_zoom(((usable_width / page_width) * 100.0).round() as u32)
 }
 
@@ -107,18 +103,60 @@ fn fit_page_percent(
     canvas_height: f32,
     page_width: f32,
     page_height: f32,
+    gap: f32,
 ) -> u32 {
     if canvas_width <= 0.0 || canvas_height <= 0.0 || page_width <= 0.0 || page_height <= 0.0 {
         return 100;
     }
 
-    let usable_width = (canvas_width - PAGE_GAP * 2.0).max(1.0);
-    let usable_height = (canvas_height - PAGE_GAP * 2.0).max(1.0);
+    let usable_width = (canvas_width - gap * 2.0).max(1.0);
+    let usable_height = (canvas_height - gap * 2.0).max(1.0);
     let width_ratio = usable_width / page_width;
     let height_ratio = usable_height / page_height;
     clamp_zoom((width_ratio.min(height_ratio) * 100.0).round() as u32)
 }
 
+/// Compute continuous-scroll page offsets for `(width, height)` page sizes.
+///
+/// Each page is preceded by `gap` pixels and the last page is followed by one more gap.
+/// Returns the per-page y offsets and the total document height.
+fn compute_page_y_offsets(dimensions: &[(f32, f32)], gap: f32) -> (Vec<f32>, f32) {
+    let mut offsets = Vec::with_capacity(dimensions.len());
+    let mut y_offset = gap;
+
+    for &(_, height) in dimensions {
+        offsets.push(y_offset);
+        y_offset += height + gap;
+    }
+
+    (offsets, y_offset)
+}
+
+/// Order visible pages so the one nearest `viewport_center` comes first.
+///
+/// Distance is measured to the nearest page edge, so a page containing the center
+/// sorts ahead of everything else; ties keep their top-to-bottom order.
+fn order_pages_by_centrality(visible: &[PageLayout], viewport_center: f32) -> Vec<u16> {
+    let mut pages = visible
+        .iter()
+        .map(|layout| {
+            let top = layout.y_offset;
+            let bottom = top + layout.height;
+            let distance = if viewport_center < top {
+                top - viewport_center
+            } else if viewport_center > bottom {
+                viewport_center - bottom
+            } else {
+                0.0
+            };
+            (layout.page_index, distance)
+        })
+        .collect::<Vec<_>>();
+
+    pages.sort_by(|a, b| a.1.total_cmp(&b.1));
+    pages.into_iter().map(|(page_index, _)| page_index).collect()
+}
+
 fn resolve_page_nav_target(current_page: u16, page_count: u16, target: PageNavTarget) -> u16 {
     if page_count == 0 {
         return 0;
@@ -133,12 +171,16 @@ fn resolve_page_nav_target(current_page: u16, page_count: u16, target: PageNavTa
     }
 }
 
-fn continuous_scroll_for_single_page(layout_y_offset: f32, single_scroll_y: f32) -> f32 {
-    (layout_y_offset - PAGE_GAP + single_scroll_y).max(0.0)
+fn continuous_scroll_for_single_page(layout_y_offset: f32, single_scroll_y: f32, gap: f32) -> f32 {
+    (layout_y_offset - gap + single_scroll_y).max(0.0)
 }
 
-fn single_page_scroll_for_continuous(layout_y_offset: f32, continuous_scroll_y: f32) -> f32 {
-    (continuous_scroll_y + PAGE_GAP - layout_y_offset).max(0.0)
+fn single_page_scroll_for_continuous(
+    layout_y_offset: f32,
+    continuous_scroll_y: f32,
+    gap: f32,
+) -> f32 {
+    (continuous_scroll_y + gap - layout_y_offset).max(0.0)
 }
 
 /// Rendered page ready for display
@@ -295,6 +337,8 @@ pub struct PdfViewport {
     page_layouts: Vec<PageLayout>,
     /// Total document height
     total_height: f32,
+    /// Gap between pages in continuous mode
+    page_gap: f32,
     /// Byte-bounded viewport surface cache.
     cache: ByteLruCache,
     /// Shared ultra-LQ preview cache consumed by viewport + sidebar.
@@ -345,6 +389,8 @@ pub struct PdfViewport {
     memory_budget: AdaptiveMemoryBudget,
     /// Dynamic targets derived from workload + adaptive budget.
     memory_targets: MemoryTargets,
+    /// RSS ratios that separate the pressure bands.
+    memory_pressure_thresholds: MemoryPressureThresholds,
     /// Current memory pressure state.
     memory_pressure_state: MemoryPressureState,
     /// Timestamp when the pressure state was entered.
@@ -534,6 +580,7 @@ impl PdfViewport {
             scroll_y: 0.0,
             page_layouts: Vec::new(),
             total_height: 0.0,
+            page_gap: PAGE_GAP,
             cache: ByteLruCache::new(memory_budget.viewport_budget_bytes),
             preview_cache,
             display_pages: Vec::new(),
@@ -559,6 +606,7 @@ impl PdfViewport {
             perf_stats: PerfStats::default(),
             memory_budget,
             memory_targets: MemoryTargets::default(),
+            memory_pressure_thresholds: MemoryPressureThresholds::detect(),
             memory_pressure_state: MemoryPressureState::Normal,
             memory_pressure_started_at: Instant::now(),
             pressure_durations_ms: PressureDurationsMs::default(),
@@ -658,39 +706,7 @@ impl PdfViewport {
     }
 
     fn derive_memory_pressure_state(&self, rss_ratio: f64, queue_hot: bool) -> MemoryPressureState {
-        let mut target = if rss_ratio >= MEMORY_PRESSURE_HOT_MAX {
-            MemoryPressureState::Critical
-        } else if rss_ratio >= MEMORY_PRESSURE_WARM_MAX {
-            MemoryPressureState::Hot
-        } else if rss_ratio >= MEMORY_PRESSURE_NORMAL_MAX {
-            MemoryPressureState::Warm
-        } else {
-            MemoryPressureState::Normal
-        };
-
-        if queue_hot && matches!(target, MemoryPressureState::Normal) {
-            target = MemoryPressureState::Warm;
-        }
-
-        // Hysteresis when moving to a less severe state.
-        match (self.memory_pressure_state, target) {
-            (MemoryPressureState::Critical, MemoryPressureState::Hot)
-                if rss_ratio > MEMORY_PRESSURE_HOT_MAX - MEMORY_PRESSURE_HYSTERESIS =>
-            {
-                MemoryPressureState::Critical
-            }
-            (MemoryPressureState::Hot, MemoryPressureState::Warm)
-                if rss_ratio > MEMORY_PRESSURE_WARM_MAX - MEMORY_PRESSURE_HYSTERESIS =>
-            {
-                MemoryPressureState::Hot
-            }
-            (MemoryPressureState::Warm, MemoryPressureState::Normal)
-                if rss_ratio > MEMORY_PRESSURE_NORMAL_MAX - MEMORY_PRESSURE_HYSTERESIS =>
-            {
-                MemoryPressureState::Warm
-            }
-            _ => target,
-        }
+        self.memory_pressure_thresholds.next_state(self.memory_pressure_state, rss_ratio, queue_hot)
     }
 
     fn visible_lq_estimate_bytes(&self) -> u64 {
@@ -1042,12 +1058,16 @@ impl PdfViewport {
 
         if let Some(layout) = self.page_layouts.get(self.current_page_index as usize) {
             self.scroll_y = match (previous_mode, self.view_mode) {
-                (ViewMode::SinglePage, ViewMode::Continuous) => {
-                    continuous_scroll_for_single_page(layout.y_offset, previous_scroll_y)
-                }
-                (ViewMode::Continuous, ViewMode::SinglePage) => {
-                    single_page_scroll_for_continuous(layout.y_offset, previous_scroll_y)
-                }
+                (ViewMode::SinglePage, ViewMode::Continuous) => continuous_scroll_for_single_page(
+                    layout.y_offset,
+                    previous_scroll_y,
+                    self.page_gap,
+                ),
+                (ViewMode::Continuous, ViewMode::SinglePage) => single_page_scroll_for_continuous(
+                    layout.y_offset,
+                    previous_scroll_y,
+                    self.page_gap,
+                ),
                 _ => previous_scroll_y,
             };
         }
@@ -1220,6 +1240,42 @@ impl PdfViewport {
         }
     }
 
+    pub fn set_page_gap(&mut self, gap: f32, cx: &mut gpui::Context<Self>) {
+        if self.set_page_gap_internal(gap) {
+            self.dispatch_render_jobs(cx);
+            cx.notify();
+        }
+    }
+
+    fn set_page_gap_internal(&mut self, gap: f32) -> bool {
+        let gap = if gap.is_finite() { gap.max(0.0) } else { PAGE_GAP };
+        if gap == self.page_gap {
+            return false;
+        }
+
+        self.bump_generation();
+        // Keep the same page under the viewport top while the layout shifts.
+        let anchor = self
+            .page_layouts
+            .get(self.current_page() as usize)
+            .map(|layout| (layout.page_index, self.scroll_y - layout.y_offset));
+
+        self.page_gap = gap;
+        self.compute_layout();
+
+        if matches!(self.view_mode, ViewMode::Continuous) {
+            if let Some((page_index, offset)) = anchor {
+                if let Some(layout) = self.page_layouts.get(page_index as usize) {
+                    self.scroll_y = layout.y_offset + offset;
+                }
+            }
+        }
+        self.clamp_scroll();
+        self.sync_scroll_handle_to_state();
+        self.update_visible_pages();
+        true
+    }
+
     pub fn reset_zoom(&mut self, cx: &mut gpui::Context<Self>) {
         self.zoom_mode = ZoomMode::Percent;
         if self.set_zoom_internal(100) {
@@ -1277,7 +1333,7 @@ impl PdfViewport {
         let Some((page_width, _)) = self.current_page_size_points() else {
             return false;
         };
-        let fit = fit_width_percent(self.canvas_width, page_width);
+        let fit = fit_width_percent(self.canvas_width, page_width, self.page_gap);
         self.set_zoom_internal(fit)
     }
 
@@ -1285,7 +1341,13 @@ impl PdfViewport {
         let Some((page_width, page_height)) = self.current_page_size_points() else {
             return false;
         };
-        let fit = fit_page_percent(self.canvas_width, self.canvas_height, page_width, page_height);
+        let fit = fit_page_percent(
+            self.canvas_width,
+            self.canvas_height,
+            page_width,
+            page_height,
+            self.page_gap,
+        );
         self.set_zoom_internal(fit)
     }
 
@@ -1326,20 +1388,28 @@ impl PdfViewport {
         let Some(doc) = &self.document else { return };
         let zoom_factor = self.zoom_level as f32 / 100.0;
 
-        let mut y_offset = PAGE_GAP;
-
+        let mut page_indexes = Vec::new();
+        let mut dimensions = Vec::new();
         for page_index in 0..doc.page_count() {
             if let Ok(page) = doc.page_dimensions(page_index) {
-                let width = page.width * zoom_factor;
-                let height = page.height * zoom_factor;
-
-                self.page_layouts.push(PageLayout { page_index, width, height, y_offset });
-
-                y_offset += height + PAGE_GAP;
+                page_indexes.push(page_index);
+                dimensions.push((page.width * zoom_factor, page.height * zoom_factor));
             }
         }
 
-        self.total_height = y_offset;
+        let (offsets, total_height) = compute_page_y_offsets(&dimensions, self.page_gap);
+        self.page_layouts = page_indexes
+            .into_iter()
+            .zip(dimensions)
+            .zip(offsets)
+            .map(|((page_index, (width, height)), y_offset)| PageLayout {
+                page_index,
+                width,
+                height,
+                y_offset,
+            })
+            .collect();
+        self.total_height = total_height;
     }
 
     fn page_indexes_within_window(&self, buffer: f32) -> HashSet<u16> {
@@ -1405,7 +1475,7 @@ impl PdfViewport {
                     page_index: layout.page_index,
                     width: layout.width,
                     height: layout.height,
-                    y_offset: PAGE_GAP,
+                    y_offset: self.page_gap,
                 })
                 .into_iter()
                 .collect::<Vec<_>>(),
@@ -1479,7 +1549,12 @@ impl PdfViewport {
 
         self.prune_cache_by_pressure();
 
-        let visible_layouts = self.visible_layouts();
+        // Queue the centered page first so it renders ahead of partially visible neighbours.
+        let mut visible_layouts = self.visible_layouts();
+        let centrality =
+            order_pages_by_centrality(&visible_layouts, self.scroll_y + self.viewport_height / 2.0);
+        visible_layouts
+            .sort_by_key(|layout| centrality.iter().position(|page| *page == layout.page_index));
         let idle = self.is_scroll_idle();
         let strict_visible = self.strict_visible_page_indexes();
         let hq_target_pages = self.hq_target_page_indexes(&strict_visible);
@@ -1923,7 +1998,7 @@ impl PdfViewport {
         let Some(layout) = self.page_layouts.get(self.current_page_index as usize) else {
             return 0.0;
         };
-        (layout.height + PAGE_GAP * 2.0 - self.viewport_height).max(0.0)
+        (layout.height + self.page_gap * 2.0 - self.viewport_height).max(0.0)
     }
 
     fn sync_scroll_from_handle(&mut self, cx: &mut gpui::Context<Self>) {
@@ -2226,8 +2301,8 @@ impl Render for PdfViewport {
         } else {
             display_pages
                 .first()
-                .map(|page| page.height as f32 + PAGE_GAP * 2.0)
-                .unwrap_or((self.viewport_height + PAGE_GAP * 2.0).max(PAGE_GAP * 2.0))
+                .map(|page| page.height as f32 + self.page_gap * 2.0)
+                .unwrap_or((self.viewport_height + self.page_gap * 2.0).max(self.page_gap * 2.0))
         };
         let scroll_handle = self.scrollbar.handle();
         let scrollbar =
@@ -2360,22 +2435,23 @@ mod tests {
     use gpui::{px, size, AppContext as _, Entity, TestAppContext};
 
     use super::{
-        continuous_scroll_for_single_page, fit_page_percent, fit_width_percent,
-        resolve_page_nav_target, single_page_scroll_for_continuous, PageLayout, PageNavTarget,
-        PageQualityState, PdfViewport, ViewMode, IDLE_SETTLE_DEBOUNCE, IDLE_TRIM_COOLDOWN,
-        PAGE_GAP, SCROLL_IDLE_DEBOUNCE, SINGLE_PAGE_IMMEDIATE_FLIP_SCROLL_EPSILON_PX,
+        compute_page_y_offsets, continuous_scroll_for_single_page, fit_page_percent,
+        fit_width_percent, order_pages_by_centrality, resolve_page_nav_target,
+        single_page_scroll_for_continuous, PageLayout, PageNavTarget, PageQualityState,
+        PdfViewport, ViewMode, IDLE_SETTLE_DEBOUNCE, IDLE_TRIM_COOLDOWN, PAGE_GAP,
+        SCROLL_IDLE_DEBOUNCE, SINGLE_PAGE_IMMEDIATE_FLIP_SCROLL_EPSILON_PX,
     };
 
     #[test]
     fn fit_width_clamps_to_zoom_limits() {
-        assert_eq!(fit_width_percent(1000.0, 500.0), 192);
-        assert_eq!(fit_width_percent(10_000.0, 10.0), 400);
-        assert_eq!(fit_width_percent(10.0, 10_000.0), 25);
+        assert_eq!(fit_width_percent(1000.0, 500.0, PAGE_GAP), 192);
+        assert_eq!(fit_width_percent(10_000.0, 10.0, PAGE_GAP), 400);
+        assert_eq!(fit_width_percent(10.0, 10_000.0, PAGE_GAP), 25);
     }
 
     #[test]
     fn fit_page_uses_smallest_ratio() {
-        let zoom = fit_page_percent(1000.0, 800.0, 400.0, 2000.0);
+        let zoom = fit_page_percent(1000.0, 800.0, 400.0, 2000.0, PAGE_GAP);
         assert_eq!(zoom, 38);
     }
 
@@ -2392,10 +2468,12 @@ mod tests {
     fn mode_switch_scroll_translation_preserves_page_top_padding() {
         let layout_y = 1260.0;
         let single_scroll = 0.0;
-        let continuous_scroll = continuous_scroll_for_single_page(layout_y, single_scroll);
+        let continuous_scroll =
+            continuous_scroll_for_single_page(layout_y, single_scroll, PAGE_GAP);
         assert_eq!(continuous_scroll, 1240.0);
 
-        let restored_single = single_page_scroll_for_continuous(layout_y, continuous_scroll);
+        let restored_single =
+            single_page_scroll_for_continuous(layout_y, continuous_scroll, PAGE_GAP);
         assert_eq!(restored_single, single_scroll);
     }
 
@@ -2403,11 +2481,64 @@ mod tests {
     fn mode_switch_scroll_translation_preserves_in_page_offset() {
         let layout_y = 700.0;
         let single_scroll = 142.5;
-        let continuous_scroll = continuous_scroll_for_single_page(layout_y, single_scroll);
-        let restored_single = single_page_scroll_for_continuous(layout_y, continuous_scroll);
+        let continuous_scroll =
+            continuous_scroll_for_single_page(layout_y, single_scroll, PAGE_GAP);
+        let restored_single =
+            single_page_scroll_for_continuous(layout_y, continuous_scroll, PAGE_GAP);
         assert!((restored_single - single_scroll).abs() < 0.001);
     }
 
+    #[test]
+    fn page_y_offsets_lead_and_trail_with_gap() {
+        let (offsets, total) = compute_page_y_offsets(&[(100.0, 200.0), (100.0, 300.0)], 20.0);
+        assert_eq!(offsets, vec![20.0, 240.0]);
+        assert_eq!(total, 560.0);
+
+        let (offsets, total) = compute_page_y_offsets(&[], 20.0);
+        assert!(offsets.is_empty());
+        assert_eq!(total, 20.0);
+    }
+
+    #[test]
+    fn changing_page_gap_shifts_offsets_and_total_height() {
+        let dimensions = [(100.0, 200.0), (100.0, 300.0), (100.0, 150.0)];
+        let (narrow, narrow_total) = compute_page_y_offsets(&dimensions, 10.0);
+        let (wide, wide_total) = compute_page_y_offsets(&dimensions, 30.0);
+
+        for (index, (narrow, wide)) in narrow.iter().zip(&wide).enumerate() {
+            assert_eq!(wide - narrow, 20.0 * (index + 1) as f32);
+        }
+        assert_eq!(wide_total - narrow_total, 20.0 * (dimensions.len() + 1) as f32);
+    }
+
+    fn stacked_layouts(count: u16) -> Vec<PageLayout> {
+        (0..count)
+            .map(|page_index| PageLayout {
+                page_index,
+                width: 400.0,
+                height: 600.0,
+                y_offset: PAGE_GAP + page_index as f32 * (600.0 + PAGE_GAP),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn centered_page_is_ordered_first() {
+        let layouts = stacked_layouts(3);
+        let center = layouts[1].y_offset + 300.0;
+        assert_eq!(order_pages_by_centrality(&layouts, center), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn centrality_prefers_the_nearer_neighbour() {
+        let layouts = stacked_layouts(3);
+        let center = layouts[1].y_offset + 550.0;
+        assert_eq!(order_pages_by_centrality(&layouts, center), vec![1, 2, 0]);
+
+        let center = layouts[2].y_offset + 10.0;
+        assert_eq!(order_pages_by_centrality(&layouts, center), vec![2, 1, 0]);
+    }
+
     fn fixture_pdf_path(name: &str) -> PathBuf {
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../tests/fixtures").join(name)
     }
@@ -1,11 +1,17 @@
 use image::{ImageBuffer, Rgba};
-use lopdf::Document;
-use std::collections::HashMap;
+use lopdf::{Document, Object, ObjectId};
+use memmap2::Mmap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::ops::Deref;
 use std::path::{Path, PathBuf};
 
 pub type RgbaImage = ImageBuffer<Rgba<u8>, Vec<u8>>;
 
+const MAX_OUTLINE_DEPTH: usize = 64;
+const DEFAULT_PAGE_SIZE: PageSize = PageSize { width_pt: 612.0, height_pt: 792.0 };
+const INHERITABLE_PAGE_KEYS: [&[u8]; 4] = [b"MediaBox", b"CropBox", b"Resources", b"Rotate"];
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct DocumentHandle(u64);
 
@@ -29,6 +35,58 @@ pub struct ClipRect {
     pub height: f32,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineItem {
+    pub title: String,
+    /// Zero-based target page, or `None` when the destination can't be resolved to a page.
+    pub page_index: Option<u32>,
+    pub children: Vec<OutlineItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidationCode {
+    Corrupt,
+    Encrypted,
+    NoPages,
+    MissingMediaBox,
+    MissingResources,
+    UnresolvedResources,
+}
+
+impl ValidationCode {
+    /// Stable machine-readable name, e.g. `MISSING_MEDIABOX`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Corrupt => "CORRUPT",
+            Self::Encrypted => "ENCRYPTED",
+            Self::NoPages => "NO_PAGES",
+            Self::MissingMediaBox => "MISSING_MEDIABOX",
+            Self::MissingResources => "MISSING_RESOURCES",
+            Self::UnresolvedResources => "UNRESOLVED_RESOURCES",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationWarning {
+    pub code: ValidationCode,
+    /// Zero-based page the warning applies to, or `None` for document-level problems.
+    pub page_index: Option<u32>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub page_count: u32,
+    pub warnings: Vec<ValidationWarning>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.warnings.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct RenderRequest {
     pub page_index: u32,
@@ -57,6 +115,8 @@ impl Default for ThumbnailSize {
 #[derive(Debug, Clone)]
 pub enum OpenSource {
     Path(PathBuf),
+    /// Memory-map the file instead of reading it; falls back to reading it if mapping fails.
+    MmapPath(PathBuf),
     Bytes(Vec<u8>),
 }
 
@@ -82,14 +142,14 @@ impl From<Vec<u8>> for OpenSource {
 pub enum PdfEngineError {
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
-    #[error("PDF parse error: {0}")]
-    Parse(#[from] lopdf::Error),
+    #[error("invalid PDF: {0}")]
+    Corrupt(String),
+    #[error("unsupported PDF: {0}")]
+    Unsupported(String),
     #[error("invalid handle {0}")]
     InvalidHandle(u64),
     #[error("page {page} out of range (page_count={page_count})")]
     PageOutOfRange { page: u32, page_count: u32 },
-    #[error("encrypted PDFs are not supported in the default backend")]
-    EncryptedUnsupported,
     #[error("backend error: {0}")]
     Backend(String),
 }
@@ -102,6 +162,7 @@ pub trait PdfEngine {
         handle: DocumentHandle,
         page_index: u32,
     ) -> Result<PageSize, PdfEngineError>;
+    fn page_sizes(&self, handle: DocumentHandle) -> Result<Vec<PageSize>, PdfEngineError>;
     fn render_page(
         &self,
         handle: DocumentHandle,
@@ -113,13 +174,46 @@ pub trait PdfEngine {
         page_index: u32,
         target: ThumbnailSize,
     ) -> Result<RgbaImage, PdfEngineError>;
+    fn outline(&self, handle: DocumentHandle) -> Result<Vec<OutlineItem>, PdfEngineError>;
     fn close(&mut self, handle: DocumentHandle) -> Result<(), PdfEngineError>;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
+enum DocumentBytes {
+    Owned(Vec<u8>),
+    Mapped(Mmap),
+}
+
+impl DocumentBytes {
+    fn map(path: &Path) -> Result<Self, PdfEngineError> {
+        let file = fs::File::open(path)?;
+        // SAFETY: the map is read-only; the file being truncated or rewritten by another
+        // process while open is the caller's concern, as with any memory-mapped reader.
+        match unsafe { Mmap::map(&file) } {
+            Ok(map) => Ok(Self::Mapped(map)),
+            // Callers can tell which path was taken via `LopdfEngine::is_memory_mapped`.
+            Err(_) => Ok(Self::Owned(fs::read(path)?)),
+        }
+    }
+}
+
+impl Deref for DocumentBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            Self::Owned(bytes) => bytes,
+            Self::Mapped(map) => map,
+        }
+    }
+}
+
+#[derive(Debug)]
 struct DocumentRecord {
-    bytes: Vec<u8>,
+    bytes: DocumentBytes,
     page_sizes: Vec<PageSize>,
+    /// 0-based indices of pages whose MediaBox was missing or degenerate
+    defaulted_pages: Vec<u32>,
 }
 
 #[derive(Debug, Default)]
@@ -133,22 +227,32 @@ impl LopdfEngine {
         Self::default()
     }
 
-    fn parse_sizes(bytes: &[u8]) -> Result<Vec<PageSize>, PdfEngineError> {
-        if bytes.windows("/Encrypt".len()).any(|window| window == b"/Encrypt") {
-            return Err(PdfEngineError::EncryptedUnsupported);
-        }
+    /// Whether the document's bytes are memory-mapped rather than read into memory.
+    ///
+    /// `OpenSource::MmapPath` falls back to reading the file when mapping fails,
+    /// so this is `false` for such documents as well as for `Path` and `Bytes`.
+    pub fn is_memory_mapped(&self, handle: DocumentHandle) -> Result<bool, PdfEngineError> {
+        Ok(matches!(self.record(handle)?.bytes, DocumentBytes::Mapped(_)))
+    }
 
-        let doc = Document::load_mem(bytes)?;
+    /// 0-based indices of pages reported at `DEFAULT_PAGE_SIZE` (US Letter)
+    /// because their MediaBox was missing or had zero area.
+    pub fn defaulted_pages(&self, handle: DocumentHandle) -> Result<&[u32], PdfEngineError> {
+        Ok(&self.record(handle)?.defaulted_pages)
+    }
+
+    /// Page sizes in page order, plus the indices of pages that fell back to
+    /// `DEFAULT_PAGE_SIZE`.
+    fn parse_sizes(bytes: &[u8]) -> Result<(Vec<PageSize>, Vec<u32>), PdfEngineError> {
+        let doc = load_document(bytes)?;
         let pages = doc.get_pages();
         let mut sizes = Vec::with_capacity(pages.len());
+        let mut defaulted = Vec::new();
 
         for (_, object_id) in pages {
-            let dict = doc.get_dictionary(object_id)?;
-            let size = dict
-                .get(b"MediaBox")
-                .ok()
-                .and_then(|obj| obj.as_array().ok())
-                .and_then(|array| {
+            let dict = doc.get_dictionary(object_id).map_err(classify_lopdf_error)?;
+            let size =
+                dict.get(b"MediaBox").ok().and_then(|obj| obj.as_array().ok()).and_then(|array| {
                     if array.len() != 4 {
                         return None;
                     }
@@ -157,8 +261,15 @@ impl LopdfEngine {
                     let x1 = array[2].as_float().ok()?;
                     let y1 = array[3].as_float().ok()?;
                     Some(PageSize { width_pt: (x1 - x0).abs(), height_pt: (y1 - y0).abs() })
-                })
-                .unwrap_or(PageSize { width_pt: 612.0, height_pt: 792.0 });
+                });
+
+            let size = match size {
+                Some(size) if size.width_pt > 0.0 && size.height_pt > 0.0 => size,
+                _ => {
+                    defaulted.push(sizes.len() as u32);
+                    DEFAULT_PAGE_SIZE
+                }
+            };
 
             sizes.push(size);
         }
@@ -167,7 +278,34 @@ impl LopdfEngine {
             return Err(PdfEngineError::Backend("document has no pages".to_owned()));
         }
 
-        Ok(sizes)
+        Ok((sizes, defaulted))
+    }
+
+    fn parse_outline(bytes: &[u8]) -> Result<Vec<OutlineItem>, PdfEngineError> {
+        let doc = load_document(bytes)?;
+        let page_indices: HashMap<ObjectId, u32> = doc
+            .get_pages()
+            .into_iter()
+            .map(|(number, id)| (id, number.saturating_sub(1)))
+            .collect();
+
+        let first = doc
+            .trailer
+            .get(b"Root")
+            .and_then(Object::as_reference)
+            .and_then(|id| doc.get_dictionary(id))
+            .and_then(|catalog| catalog.get(b"Outlines"))
+            .and_then(|outlines| doc.dereference(outlines))
+            .and_then(|(_, outlines)| outlines.as_dict())
+            .and_then(|outlines| outlines.get(b"First"))
+            .and_then(Object::as_reference);
+
+        let Ok(first) = first else {
+            return Ok(Vec::new());
+        };
+
+        let mut visited = HashSet::new();
+        Ok(outline_items(&doc, first, &page_indices, &mut visited, 0))
     }
 
     fn record(&self, handle: DocumentHandle) -> Result<&DocumentRecord, PdfEngineError> {
@@ -178,15 +316,16 @@ impl LopdfEngine {
 impl PdfEngine for LopdfEngine {
     fn open(&mut self, source: OpenSource) -> Result<DocumentHandle, PdfEngineError> {
         let bytes = match source {
-            OpenSource::Path(path) => fs::read(path)?,
-            OpenSource::Bytes(bytes) => bytes,
+            OpenSource::Path(path) => DocumentBytes::Owned(fs::read(path)?),
+            OpenSource::MmapPath(path) => DocumentBytes::map(&path)?,
+            OpenSource::Bytes(bytes) => DocumentBytes::Owned(bytes),
         };
 
-        let page_sizes = Self::parse_sizes(&bytes)?;
+        let (page_sizes, defaulted_pages) = Self::parse_sizes(&bytes)?;
 
         self.next_handle += 1;
         let handle = DocumentHandle(self.next_handle);
-        self.docs.insert(handle, DocumentRecord { bytes, page_sizes });
+        self.docs.insert(handle, DocumentRecord { bytes, page_sizes, defaulted_pages });
 
         Ok(handle)
     }
@@ -207,6 +346,10 @@ impl PdfEngine for LopdfEngine {
         })
     }
 
+    fn page_sizes(&self, handle: DocumentHandle) -> Result<Vec<PageSize>, PdfEngineError> {
+        Ok(self.record(handle)?.page_sizes.clone())
+    }
+
     fn render_page(
         &self,
         handle: DocumentHandle,
@@ -252,11 +395,309 @@ impl PdfEngine for LopdfEngine {
         Ok(image::imageops::thumbnail(&page, target.width_px.max(1), target.height_px.max(1)))
     }
 
+    fn outline(&self, handle: DocumentHandle) -> Result<Vec<OutlineItem>, PdfEngineError> {
+        Self::parse_outline(&self.record(handle)?.bytes)
+    }
+
     fn close(&mut self, handle: DocumentHandle) -> Result<(), PdfEngineError> {
         self.docs.remove(&handle).map(|_| ()).ok_or(PdfEngineError::InvalidHandle(handle.raw()))
     }
 }
 
+/// Concatenates the pages of `inputs`, in order, into a new PDF.
+///
+/// Object ids are offset per input so they can't collide. Page attributes
+/// inherited from each input's page tree (media box, resources, ...) are
+/// copied onto the pages so sizes survive the new tree.
+pub fn merge_pdfs(inputs: &[Vec<u8>]) -> Result<Vec<u8>, PdfEngineError> {
+    if inputs.is_empty() {
+        return Err(PdfEngineError::Backend("no documents to merge".to_owned()));
+    }
+
+    let mut merged = Document::with_version("1.5");
+    let mut page_ids = Vec::new();
+    let mut next_id = 1;
+
+    for bytes in inputs {
+        let mut doc = load_document(bytes)?;
+        doc.renumber_objects_with(next_id);
+        next_id = doc.max_id + 1;
+
+        let pages: Vec<ObjectId> = doc.get_pages().into_values().collect();
+        for &page_id in &pages {
+            inherit_page_attributes(&mut doc, page_id);
+        }
+
+        for (id, object) in doc.objects {
+            let type_name = object
+                .as_dict()
+                .and_then(|dict| dict.get(b"Type"))
+                .and_then(Object::as_name)
+                .unwrap_or_default();
+            if type_name != b"Catalog" && type_name != b"Pages" {
+                merged.objects.insert(id, object);
+            }
+        }
+        page_ids.extend(pages);
+    }
+
+    if page_ids.is_empty() {
+        return Err(PdfEngineError::Backend("document has no pages".to_owned()));
+    }
+
+    let pages_id = (next_id, 0);
+    let catalog_id = (next_id + 1, 0);
+
+    for &page_id in &page_ids {
+        if let Ok(page) = merged.get_object_mut(page_id).and_then(Object::as_dict_mut) {
+            page.set("Parent", pages_id);
+        }
+    }
+
+    let mut pages = lopdf::Dictionary::new();
+    pages.set("Type", Object::Name(b"Pages".to_vec()));
+    pages.set("Kids", page_ids.iter().copied().map(Object::Reference).collect::<Vec<_>>());
+    pages.set("Count", page_ids.len() as i64);
+    merged.objects.insert(pages_id, Object::Dictionary(pages));
+
+    let mut catalog = lopdf::Dictionary::new();
+    catalog.set("Type", Object::Name(b"Catalog".to_vec()));
+    catalog.set("Pages", pages_id);
+    merged.objects.insert(catalog_id, Object::Dictionary(catalog));
+
+    merged.trailer.set("Root", catalog_id);
+    merged.max_id = catalog_id.0;
+    // Drops outlines and other objects that only the skipped catalogs referenced.
+    merged.prune_objects();
+
+    let mut output = Vec::new();
+    merged
+        .save_to(&mut output)
+        .map_err(|err| PdfEngineError::Backend(format!("failed to write merged PDF: {err}")))?;
+    Ok(output)
+}
+
+/// Walks the page tree and reports structural problems instead of failing on them.
+///
+/// Unreadable and encrypted files produce a single document-level warning.
+pub fn validate_pdf(bytes: &[u8]) -> ValidationReport {
+    let doc = match load_document(bytes) {
+        Ok(doc) => doc,
+        Err(err) => {
+            let code = if is_encrypted(bytes) {
+                ValidationCode::Encrypted
+            } else {
+                ValidationCode::Corrupt
+            };
+            return ValidationReport {
+                page_count: 0,
+                warnings: vec![ValidationWarning {
+                    code,
+                    page_index: None,
+                    message: err.to_string(),
+                }],
+            };
+        }
+    };
+
+    let pages = doc.get_pages();
+    let mut report = ValidationReport { page_count: pages.len() as u32, warnings: Vec::new() };
+    if pages.is_empty() {
+        report.warnings.push(ValidationWarning {
+            code: ValidationCode::NoPages,
+            page_index: None,
+            message: "document has no pages".to_owned(),
+        });
+    }
+
+    for (page_number, page_id) in pages {
+        let page_index = Some(page_number.saturating_sub(1));
+        let mut warn = |code, message: &str| {
+            report.warnings.push(ValidationWarning {
+                code,
+                page_index,
+                message: format!("page {page_number}: {message}"),
+            });
+        };
+
+        if inherited_page_attribute(&doc, page_id, b"MediaBox").is_none() {
+            warn(ValidationCode::MissingMediaBox, "no MediaBox on the page or its ancestors");
+        }
+
+        match inherited_page_attribute(&doc, page_id, b"Resources") {
+            None => {
+                warn(ValidationCode::MissingResources, "no Resources on the page or its ancestors")
+            }
+            Some(resources) => {
+                if doc.dereference(resources).and_then(|(_, object)| object.as_dict()).is_err() {
+                    warn(
+                        ValidationCode::UnresolvedResources,
+                        "Resources do not resolve to a dictionary",
+                    );
+                }
+            }
+        }
+    }
+
+    report
+}
+
+/// Looks up an inheritable page attribute on the page or the nearest ancestor that has it.
+fn inherited_page_attribute<'a>(
+    doc: &'a Document,
+    page_id: ObjectId,
+    key: &[u8],
+) -> Option<&'a Object> {
+    let mut node_id = Some(page_id);
+    let mut visited = HashSet::new();
+
+    while let Some(id) = node_id {
+        if !visited.insert(id) {
+            break;
+        }
+        let node = doc.get_dictionary(id).ok()?;
+        if let Ok(value) = node.get(key) {
+            return Some(value);
+        }
+        node_id = node.get(b"Parent").and_then(Object::as_reference).ok();
+    }
+
+    None
+}
+
+/// Loads a document, splitting failures into files that are broken and files
+/// that are valid but use something this backend can't handle.
+fn load_document(bytes: &[u8]) -> Result<Document, PdfEngineError> {
+    if is_encrypted(bytes) {
+        return Err(PdfEngineError::Unsupported(
+            "encrypted PDFs are not supported in the default backend".to_owned(),
+        ));
+    }
+
+    Document::load_mem(bytes).map_err(classify_lopdf_error)
+}
+
+/// Maps a lopdf error to `Unsupported` when it names a feature lopdf lacks and
+/// to `Corrupt` otherwise.
+fn classify_lopdf_error(err: lopdf::Error) -> PdfEngineError {
+    match err {
+        lopdf::Error::Unimplemented(feature) => PdfEngineError::Unsupported(feature.to_string()),
+        other => PdfEngineError::Corrupt(other.to_string()),
+    }
+}
+
+fn is_encrypted(bytes: &[u8]) -> bool {
+    bytes.windows("/Encrypt".len()).any(|window| window == b"/Encrypt")
+}
+
+fn inherit_page_attributes(doc: &mut Document, page_id: ObjectId) {
+    let Ok(page) = doc.get_dictionary(page_id) else {
+        return;
+    };
+
+    let mut missing: Vec<&[u8]> =
+        INHERITABLE_PAGE_KEYS.iter().copied().filter(|key| !page.has(key)).collect();
+    let mut parent = page.get(b"Parent").and_then(Object::as_reference).ok();
+    let mut inherited = Vec::new();
+    let mut visited = HashSet::new();
+
+    while let Some(id) = parent {
+        if missing.is_empty() || !visited.insert(id) {
+            break;
+        }
+        let Ok(node) = doc.get_dictionary(id) else {
+            break;
+        };
+
+        missing.retain(|key| match node.get(key) {
+            Ok(value) => {
+                inherited.push((key.to_vec(), value.clone()));
+                false
+            }
+            Err(_) => true,
+        });
+        parent = node.get(b"Parent").and_then(Object::as_reference).ok();
+    }
+
+    if let Ok(page) = doc.get_object_mut(page_id).and_then(Object::as_dict_mut) {
+        for (key, value) in inherited {
+            page.set(key, value);
+        }
+    }
+}
+
+fn outline_items(
+    doc: &Document,
+    first: ObjectId,
+    page_indices: &HashMap<ObjectId, u32>,
+    visited: &mut HashSet<ObjectId>,
+    depth: usize,
+) -> Vec<OutlineItem> {
+    let mut items = Vec::new();
+    if depth >= MAX_OUTLINE_DEPTH {
+        return items;
+    }
+
+    let mut next = Some(first);
+    while let Some(id) = next {
+        // Malformed files can link siblings or children back into a cycle.
+        if !visited.insert(id) {
+            break;
+        }
+        let Ok(dict) = doc.get_dictionary(id) else {
+            break;
+        };
+
+        let title = dict
+            .get(b"Title")
+            .and_then(|title| doc.dereference(title))
+            .ok()
+            .and_then(|(_, title)| match title {
+                Object::String(bytes, _) => Some(decode_text_string(bytes)),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let page_index =
+            outline_destination_page(doc, dict).and_then(|page| page_indices.get(&page).copied());
+
+        let children = dict
+            .get(b"First")
+            .and_then(Object::as_reference)
+            .map(|child| outline_items(doc, child, page_indices, visited, depth + 1))
+            .unwrap_or_default();
+
+        items.push(OutlineItem { title, page_index, children });
+        next = dict.get(b"Next").and_then(Object::as_reference).ok();
+    }
+
+    items
+}
+
+fn outline_destination_page(doc: &Document, item: &lopdf::Dictionary) -> Option<ObjectId> {
+    let dest = match item.get(b"Dest") {
+        Ok(dest) => dest,
+        Err(_) => {
+            let (_, action) = doc.dereference(item.get(b"A").ok()?).ok()?;
+            action.as_dict().ok()?.get(b"D").ok()?
+        }
+    };
+
+    // Named destinations are not resolved; only explicit `[page /Fit ...]` arrays are.
+    let (_, dest) = doc.dereference(dest).ok()?;
+    dest.as_array().ok()?.first()?.as_reference().ok()
+}
+
+fn decode_text_string(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> =
+            utf16.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+        return String::from_utf16_lossy(&units);
+    }
+
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
 #[cfg(feature = "pdfium")]
 pub mod pdfium_backend {
     use super::*;
@@ -293,6 +734,10 @@ pub mod pdfium_backend {
             self.inner.page_size(handle, page_index)
         }
 
+        fn page_sizes(&self, handle: DocumentHandle) -> Result<Vec<PageSize>, PdfEngineError> {
+            self.inner.page_sizes(handle)
+        }
+
         fn render_page(
             &self,
             handle: DocumentHandle,
@@ -310,6 +755,10 @@ pub mod pdfium_backend {
             self.inner.render_thumbnail(handle, page_index, target)
         }
 
+        fn outline(&self, handle: DocumentHandle) -> Result<Vec<OutlineItem>, PdfEngineError> {
+            self.inner.outline(handle)
+        }
+
         fn close(&mut self, handle: DocumentHandle) -> Result<(), PdfEngineError> {
             self.inner.close(handle)
         }
@@ -338,6 +787,37 @@ mod tests {
         assert_eq!(engine.page_count(handle).expect("count should succeed"), 1);
     }
 
+    #[test]
+    fn mmap_open_matches_byte_open() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../../tests/fixtures")
+            .join("mixed-sizes.pdf");
+        let mut engine = LopdfEngine::new();
+        let mapped =
+            engine.open(OpenSource::MmapPath(path.clone())).expect("mmap open should succeed");
+        let read = engine
+            .open(OpenSource::Bytes(fs::read(&path).expect("fixture should be readable")))
+            .expect("byte open should succeed");
+
+        assert!(engine.is_memory_mapped(mapped).expect("handle should be valid"));
+        assert!(!engine.is_memory_mapped(read).expect("handle should be valid"));
+
+        let page_count = engine.page_count(mapped).expect("count should succeed");
+        assert_eq!(page_count, 3);
+        assert_eq!(page_count, engine.page_count(read).expect("count should succeed"));
+
+        for page_index in 0..page_count {
+            assert_eq!(
+                engine.page_size(mapped, page_index).expect("size should succeed"),
+                engine.page_size(read, page_index).expect("size should succeed")
+            );
+        }
+        assert_eq!(
+            engine.page_size(mapped, 1).expect("size should succeed"),
+            PageSize { width_pt: 792.0, height_pt: 1224.0 }
+        );
+    }
+
     #[test]
     fn render_thumbnail_produces_non_empty_image() {
         let mut engine = LopdfEngine::new();
@@ -353,6 +833,192 @@ mod tests {
         assert!(image.height() > 0);
     }
 
+    #[test]
+    fn outline_preserves_nesting_and_targets() {
+        let mut engine = LopdfEngine::new();
+        let handle = engine
+            .open(OpenSource::Bytes(include_bytes!("../../../tests/fixtures/outline.pdf").to_vec()))
+            .expect("open should succeed");
+
+        let outline = engine.outline(handle).expect("outline should parse");
+
+        assert_eq!(outline.len(), 2);
+        assert_eq!(outline[0].title, "Chapter 1");
+        assert_eq!(outline[0].page_index, Some(0));
+        assert_eq!(outline[0].children.len(), 2);
+        assert_eq!(outline[0].children[1].title, "Section 1.2");
+        assert_eq!(outline[0].children[1].children[0].title, "Details");
+        assert_eq!(outline[0].children[1].children[0].page_index, Some(2));
+        assert_eq!(outline[1].title, "Chapter 2");
+        assert_eq!(outline[1].page_index, Some(3));
+        assert!(outline[1].children.is_empty());
+    }
+
+    #[test]
+    fn outline_is_empty_without_bookmarks() {
+        let mut engine = LopdfEngine::new();
+        let handle = engine
+            .open(OpenSource::Bytes(sample_pdf_bytes().to_vec()))
+            .expect("open should succeed");
+
+        assert!(engine.outline(handle).expect("outline should parse").is_empty());
+    }
+
+    #[test]
+    fn decodes_utf16_outline_titles() {
+        assert_eq!(decode_text_string(&[0xFE, 0xFF, 0x00, 0x41, 0x00, 0xE9]), "A\u{e9}");
+        assert_eq!(decode_text_string(b"Intro"), "Intro");
+    }
+
+    #[test]
+    fn merge_concatenates_pages_in_order() {
+        let small = sample_pdf_bytes().to_vec();
+        let medium = include_bytes!("../../../tests/fixtures/medium.pdf").to_vec();
+
+        let merged = merge_pdfs(&[small, medium]).expect("merge should succeed");
+
+        let mut engine = LopdfEngine::new();
+        let handle = engine.open(OpenSource::Bytes(merged)).expect("merged pdf should open");
+        assert_eq!(engine.page_count(handle).expect("count should succeed"), 6);
+        assert_eq!(
+            engine.page_size(handle, 5).expect("size should succeed"),
+            PageSize { width_pt: 612.0, height_pt: 792.0 }
+        );
+    }
+
+    #[test]
+    fn merge_preserves_each_page_size() {
+        let small = sample_pdf_bytes().to_vec();
+        let mixed = include_bytes!("../../../tests/fixtures/mixed-sizes.pdf").to_vec();
+
+        let merged = merge_pdfs(&[small, mixed]).expect("merge should succeed");
+
+        let mut engine = LopdfEngine::new();
+        let handle = engine.open(OpenSource::Bytes(merged)).expect("merged pdf should open");
+        let letter = PageSize { width_pt: 612.0, height_pt: 792.0 };
+        let tabloid = PageSize { width_pt: 792.0, height_pt: 1224.0 };
+        assert_eq!(
+            engine.page_sizes(handle).expect("sizes should succeed"),
+            vec![letter, letter, tabloid, letter]
+        );
+        assert!(engine.defaulted_pages(handle).expect("handle should be valid").is_empty());
+    }
+
+    #[test]
+    fn merge_copies_media_box_inherited_from_the_page_tree() {
+        let small = sample_pdf_bytes().to_vec();
+        let inherited = include_bytes!("../../../tests/fixtures/inherited-mediabox.pdf").to_vec();
+
+        let merged = merge_pdfs(&[small, inherited]).expect("merge should succeed");
+
+        let mut engine = LopdfEngine::new();
+        let handle = engine.open(OpenSource::Bytes(merged)).expect("merged pdf should open");
+        let tabloid = PageSize { width_pt: 792.0, height_pt: 1224.0 };
+        assert_eq!(engine.page_count(handle).expect("count should succeed"), 3);
+        assert_eq!(engine.page_size(handle, 1).expect("size should succeed"), tabloid);
+        assert_eq!(engine.page_size(handle, 2).expect("size should succeed"), tabloid);
+        assert!(engine.defaulted_pages(handle).expect("handle should be valid").is_empty());
+    }
+
+    #[test]
+    fn merge_rejects_empty_input() {
+        assert!(matches!(merge_pdfs(&[]), Err(PdfEngineError::Backend(_))));
+    }
+
+    #[test]
+    fn zero_area_media_box_falls_back_to_default_size() {
+        let mut doc = Document::load_mem(include_bytes!("../../../tests/fixtures/medium.pdf"))
+            .expect("fixture should load");
+        let second_page = doc.get_pages()[&2];
+        doc.get_object_mut(second_page)
+            .and_then(Object::as_dict_mut)
+            .expect("page should be a dictionary")
+            .set("MediaBox", [0, 0, 0, 792].map(Object::Integer).to_vec());
+        let mut bytes = Vec::new();
+        doc.save_to(&mut bytes).expect("save should succeed");
+
+        let mut engine = LopdfEngine::new();
+        let handle = engine.open(OpenSource::Bytes(bytes)).expect("open should succeed");
+
+        assert_eq!(engine.page_count(handle).expect("count should succeed"), 5);
+        assert_eq!(engine.page_size(handle, 1).expect("size should succeed"), DEFAULT_PAGE_SIZE);
+        assert_eq!(engine.defaulted_pages(handle).expect("handle should be valid"), [1]);
+    }
+
+    #[test]
+    fn garbage_bytes_are_reported_as_corrupt() {
+        let mut engine = LopdfEngine::new();
+        let err = engine
+            .open(OpenSource::Bytes(b"definitely not a pdf".to_vec()))
+            .expect_err("garbage should not open");
+
+        assert!(matches!(err, PdfEngineError::Corrupt(_)));
+    }
+
+    #[test]
+    fn encrypted_pdf_is_reported_as_unsupported() {
+        let bytes = include_bytes!("../../../tests/fixtures/encrypted-marker.pdf").to_vec();
+
+        let mut engine = LopdfEngine::new();
+        let err = engine.open(OpenSource::Bytes(bytes)).expect_err("encrypted pdf should not open");
+
+        assert!(matches!(err, PdfEngineError::Unsupported(_)));
+        assert!(err.to_string().contains("encrypted PDFs are not supported"));
+    }
+
+    #[test]
+    fn validate_accepts_clean_fixture() {
+        let report = validate_pdf(include_bytes!("../../../tests/fixtures/medium.pdf"));
+
+        assert!(report.is_ok(), "unexpected warnings: {:?}", report.warnings);
+        assert_eq!(report.page_count, 5);
+    }
+
+    #[test]
+    fn validate_reports_missing_media_box() {
+        let report = validate_pdf(include_bytes!("../../../tests/fixtures/missing-mediabox.pdf"));
+
+        assert!(!report.is_ok());
+        assert_eq!(report.page_count, 2);
+        assert_eq!(report.warnings.len(), 1);
+        assert_eq!(report.warnings[0].code, ValidationCode::MissingMediaBox);
+        assert_eq!(report.warnings[0].page_index, Some(1));
+    }
+
+    #[test]
+    fn validate_reports_encrypted_and_corrupt_files() {
+        let encrypted =
+            validate_pdf(include_bytes!("../../../tests/fixtures/encrypted-marker.pdf"));
+        assert_eq!(encrypted.warnings[0].code, ValidationCode::Encrypted);
+
+        let corrupt = validate_pdf(include_bytes!("../../../tests/fixtures/invalid.pdf"));
+        assert_eq!(corrupt.warnings[0].code, ValidationCode::Corrupt);
+        assert_eq!(corrupt.page_count, 0);
+    }
+
+    #[test]
+    fn page_sizes_match_per_page_queries() {
+        let mut engine = LopdfEngine::new();
+        let handle = engine
+            .open(OpenSource::Bytes(
+                include_bytes!("../../../tests/fixtures/mixed-sizes.pdf").to_vec(),
+            ))
+            .expect("fixture should open");
+
+        let sizes = engine.page_sizes(handle).expect("sizes should succeed");
+        assert_eq!(sizes.len() as u32, engine.page_count(handle).expect("count should succeed"));
+        for (page_index, size) in sizes.iter().enumerate() {
+            assert_eq!(
+                *size,
+                engine.page_size(handle, page_index as u32).expect("size should succeed")
+            );
+        }
+
+        let err =
+            engine.page_sizes(DocumentHandle(999)).expect_err("should fail for unknown handle");
+        assert!(matches!(err, PdfEngineError::InvalidHandle(999)));
+    }
+
     #[test]
     fn invalid_handle_returns_error() {
         let engine = LopdfEngine::new();
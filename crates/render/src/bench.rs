@@ -0,0 +1,74 @@
+//! Headless render benchmarking
+//!
+//! Renders pages straight through `PdfDocument` without any GUI so the
+//! rasterization cost can be measured from `cargo bench` or the CLI.
+
+use crate::pdf::{PdfDocument, PdfError, PdfResult};
+use crate::RenderQuality;
+use std::time::{Duration, Instant};
+
+/// Render each requested page once and report how long it took
+///
+/// The document handle is reused for every page. All page indices are checked
+/// before any rendering starts, so an out-of-range page fails fast.
+///
+/// # Arguments
+/// * `pdf` - Open document to render from
+/// * `pages` - Zero-based page indices, rendered in the given order
+/// * `scale` - Pixels per PDF point (1.0 renders at 72 DPI)
+///
+/// # Returns
+/// One `(page_index, duration)` pair per requested page, in request order
+pub fn render_document_pages(
+    pdf: &PdfDocument,
+    pages: &[u16],
+    scale: f32,
+) -> PdfResult<Vec<(u16, Duration)>> {
+    if !scale.is_finite() || scale <= 0.0 {
+        return Err(PdfError::RenderError(format!("Invalid render scale: {}", scale)));
+    }
+
+    let page_count = pdf.page_count();
+    if let Some(&page_index) = pages.iter().find(|&&page_index| page_index >= page_count) {
+        return Err(PdfError::InvalidPageIndex(page_index));
+    }
+
+    let mut timings = Vec::with_capacity(pages.len());
+    for &page_index in pages {
+        let dimensions = pdf.page_dimensions(page_index)?;
+        let width = (dimensions.width * scale).round().max(1.0) as u32;
+        let height = (dimensions.height * scale).round().max(1.0) as u32;
+
+        let started = Instant::now();
+        pdf.render_page_rgba_with_quality(page_index, width, height, RenderQuality::HqFinal)?;
+        timings.push((page_index, started.elapsed()));
+    }
+
+    Ok(timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf::open_fixture;
+
+    #[test]
+    fn test_render_document_pages_times_each_page() {
+        let pdf = open_fixture("medium.pdf");
+        let pages = [0, 2, 0];
+
+        let timings = render_document_pages(&pdf, &pages, 0.25).unwrap();
+
+        assert_eq!(timings.iter().map(|(page, _)| *page).collect::<Vec<_>>(), pages);
+    }
+
+    #[test]
+    fn test_render_document_pages_rejects_out_of_range_page() {
+        let pdf = open_fixture("medium.pdf");
+        let out_of_range = pdf.page_count();
+
+        let result = render_document_pages(&pdf, &[0, out_of_range], 0.25);
+
+        assert!(matches!(result, Err(PdfError::InvalidPageIndex(index)) if index == out_of_range));
+    }
+}
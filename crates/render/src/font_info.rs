@@ -13,6 +13,18 @@
 use pdfium_render::prelude::*;
 use std::collections::HashMap;
 
+/// Font descriptor flag: all glyphs have the same width
+pub const FONT_FLAG_FIXED_PITCH: u32 = 1 << 0;
+
+/// Font descriptor flag: glyphs have serifs
+pub const FONT_FLAG_SERIF: u32 = 1 << 1;
+
+/// Font descriptor flag: glyphs are slanted
+pub const FONT_FLAG_ITALIC: u32 = 1 << 6;
+
+/// Font descriptor flag: bold glyphs should be emboldened at small sizes
+pub const FONT_FLAG_FORCE_BOLD: u32 = 1 << 18;
+
 /// Information about a font used in a PDF page
 #[derive(Debug, Clone, PartialEq)]
 pub struct FontInfo {
@@ -33,6 +45,9 @@ pub struct FontInfo {
 
     /// Whether the font is bold
     pub is_bold: bool,
+
+    /// Whether the font was read from the PDF; false for a best-effort fallback
+    pub resolved: bool,
 }
 
 impl FontInfo {
@@ -42,7 +57,66 @@ impl FontInfo {
         let is_italic =
             name.to_lowercase().contains("italic") || name.to_lowercase().contains("oblique");
 
-        Self { name, is_standard: false, is_embedded: false, weight: None, is_italic, is_bold }
+        Self {
+            name,
+            is_standard: false,
+            is_embedded: false,
+            weight: None,
+            is_italic,
+            is_bold,
+            resolved: true,
+        }
+    }
+
+    /// Create a best-effort standard font when the real font can't be resolved
+    ///
+    /// The base-14 family is guessed from the font descriptor flags (fixed pitch
+    /// maps to Courier, serif to Times, anything else to Helvetica), with bold and
+    /// italic taken from the flags or from hints in the (possibly empty) name.
+    ///
+    /// # Arguments
+    /// * `name` - Whatever name the font dictionary reported, if any
+    /// * `flags` - PDF font descriptor flags (`FONT_FLAG_*`)
+    ///
+    /// # Returns
+    /// A standard font info with `resolved` set to false
+    pub fn fallback(name: &str, flags: u32) -> Self {
+        let hints = Self::new(name.to_string());
+        let is_bold = hints.is_bold || flags & FONT_FLAG_FORCE_BOLD != 0;
+        let is_italic = hints.is_italic || flags & FONT_FLAG_ITALIC != 0;
+
+        let name = if flags & FONT_FLAG_FIXED_PITCH != 0 {
+            match (is_bold, is_italic) {
+                (false, false) => "Courier",
+                (true, false) => "Courier-Bold",
+                (false, true) => "Courier-Oblique",
+                (true, true) => "Courier-BoldOblique",
+            }
+        } else if flags & FONT_FLAG_SERIF != 0 {
+            match (is_bold, is_italic) {
+                (false, false) => "Times-Roman",
+                (true, false) => "Times-Bold",
+                (false, true) => "Times-Italic",
+                (true, true) => "Times-BoldItalic",
+            }
+        } else {
+            match (is_bold, is_italic) {
+                (false, false) => "Helvetica",
+                (true, false) => "Helvetica-Bold",
+                (false, true) => "Helvetica-Oblique",
+                (true, true) => "Helvetica-BoldOblique",
+            }
+        };
+
+        Self {
+            name: name.to_string(),
+            is_standard: true,
+            is_embedded: false,
+            weight: None,
+            is_italic,
+            is_bold,
+            resolved: false,
+        }
     }
 
     /// Check if this is one of the 14 standard PDF fonts
@@ -138,12 +212,31 @@ pub fn extract_fonts_from_page(page: &PdfPage) -> Result<Vec<TextSpanWithFont>,
         // PdfPageTextObject doesn't have font_size() method, use bounds height as estimate
         let font_size = bounds.height().value;
 
-        // Create font info
-        let mut font_info = FontInfo::new(font_name);
-        font_info.check_is_standard();
-
-        // Check if font is embedded (returns Result<bool>)
-        font_info.is_embedded = font.is_embedded().unwrap_or(false);
+        // Create font info, falling back to a standard font when the
+        // font dictionary doesn't give us a usable name
+        let font_info = if font_name.trim().is_empty() {
+            let mut flags = 0;
+            if font.is_fixed_pitch() {
+                flags |= FONT_FLAG_FIXED_PITCH;
+            }
+            if font.is_serif() {
+                flags |= FONT_FLAG_SERIF;
+            }
+            if font.is_italic() {
+                flags |= FONT_FLAG_ITALIC;
+            }
+            if font.is_bold_reenforced() {
+                flags |= FONT_FLAG_FORCE_BOLD;
+            }
+            FontInfo::fallback(&font_name, flags)
+        } else {
+            let mut font_info = FontInfo::new(font_name);
+            font_info.check_is_standard();
+
+            // Check if font is embedded (returns Result<bool>)
+            font_info.is_embedded = font.is_embedded().unwrap_or(false);
+            font_info
+        };
 
         spans.push(TextSpanWithFont { text, bbox, font_size, font: font_info });
     }
@@ -270,6 +363,45 @@ mod tests {
         assert!(!font4.is_standard);
     }
 
+    #[test]
+    fn test_font_info_new_is_resolved() {
+        let font = FontInfo::new("Arial".to_string());
+        assert!(font.resolved);
+    }
+
+    #[test]
+    fn test_fallback_for_malformed_font_is_flagged() {
+        let font = FontInfo::fallback("", 0);
+        assert!(!font.resolved);
+        assert!(font.is_standard);
+        assert_eq!(font.name, "Helvetica");
+    }
+
+    #[test]
+    fn test_fallback_guesses_family_from_flags() {
+        let serif = FontInfo::fallback("", FONT_FLAG_SERIF | FONT_FLAG_ITALIC);
+        assert_eq!(serif.name, "Times-Italic");
+        assert!(serif.is_italic);
+
+        let mono = FontInfo::fallback("", FONT_FLAG_FIXED_PITCH | FONT_FLAG_FORCE_BOLD);
+        assert_eq!(mono.name, "Courier-Bold");
+        assert!(mono.is_bold);
+
+        let sans = FontInfo::fallback("", FONT_FLAG_ITALIC | FONT_FLAG_FORCE_BOLD);
+        assert_eq!(sans.name, "Helvetica-BoldOblique");
+    }
+
+    #[test]
+    fn test_fallback_uses_style_hints_from_name() {
+        let font = FontInfo::fallback("Garbled-BoldItalic", FONT_FLAG_SERIF);
+        assert_eq!(font.name, "Times-BoldItalic");
+        assert!(!font.resolved);
+
+        let mut standard = font.clone();
+        standard.check_is_standard();
+        assert!(standard.is_standard);
+    }
+
     #[test]
     fn test_all_14_standard_fonts() {
         let standard_fonts = vec![
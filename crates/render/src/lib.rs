@@ -2,10 +2,17 @@
 //!
 //! PDF render pipeline with tile-based rendering, preview and crisp profiles.
 
+pub mod bench;
 pub mod font_info;
+mod lru;
 pub mod pdf;
+pub mod pixel;
 pub mod progressive;
+pub mod render_cache;
+pub mod thumbnail;
 pub mod tile;
+pub mod timing;
+pub mod tint;
 
 /// Rendering quality profiles for staged PDF rasterization.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -18,12 +25,26 @@ pub enum RenderQuality {
     HqFinal,
 }
 
+pub use bench::render_document_pages;
 pub use font_info::{
     extract_fonts_from_page, find_font_in_region, get_page_fonts, FontInfo, TextSpanWithFont,
+    FONT_FLAG_FIXED_PITCH, FONT_FLAG_FORCE_BOLD, FONT_FLAG_ITALIC, FONT_FLAG_SERIF,
 };
 pub use pdf::{
-    detect_needs_ocr, PageDimensions, PdfDocument, PdfError, PdfMetadata, PdfResult, SaveError,
-    TextSpanInfo,
+    clamp_render_scale, detect_needs_ocr, ocr_confidence, resolve_page_label_in, ExtractedImage,
+    ExtractedImageFormat, PageDimensions, PageDimensionsIter, PageImages, PdfDocument, PdfError,
+    PdfMetadata, PdfResult, SaveError, SkipReason, SkippedImage, TextSpanInfo,
+    DEFAULT_MAX_RENDER_PIXELS, DEFAULT_OCR_CONFIDENCE_THRESHOLD,
 };
+pub use pixel::{rgba_to_bgra, rgba_to_bgra_in_place};
 pub use progressive::{ProgressCallback, ProgressiveTileLoader, TileState};
-pub use tile::{RenderedTile, TileCoordinate, TileId, TileProfile, TileRenderer};
+pub use render_cache::{RenderCache, RenderCacheKey, DEFAULT_RENDER_CACHE_CAPACITY};
+pub use thumbnail::{
+    CachedThumbnail, ThumbnailCache, ThumbnailKey, DEFAULT_THUMBNAIL_CACHE_CAPACITY,
+};
+pub use tile::{
+    select_profile, ColorSpace, RenderedTile, TileCoordinate, TileId, TileProfile, TileRenderer,
+    CRISP_SCROLL_VELOCITY, PREVIEW_SCROLL_VELOCITY,
+};
+pub use timing::RenderTimings;
+pub use tint::{apply_page_tint, apply_page_tint_preserving, PageTint};
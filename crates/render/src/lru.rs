@@ -0,0 +1,122 @@
+//! Small least-recently-used map shared by the render caches
+//!
+//! Entries are stamped with a logical clock on every access; eviction scans
+//! for the oldest stamp. Caches here hold at most a few hundred entries, so
+//! the linear scan is cheaper than maintaining a linked list.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Debug)]
+struct Slot<V> {
+    value: V,
+    last_used: u64,
+}
+
+/// Map that evicts its least recently used entry once it holds `capacity` entries
+#[derive(Debug)]
+pub(crate) struct LruMap<K, V> {
+    entries: HashMap<K, Slot<V>>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl<K: Copy + Eq + Hash, V> LruMap<K, V> {
+    /// Create a map holding up to `capacity` entries (at least one)
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self { entries: HashMap::new(), capacity: capacity.max(1), clock: 0 }
+    }
+
+    /// Look up an entry and mark it as most recently used
+    pub(crate) fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.clock += 1;
+        let slot = self.entries.get_mut(key)?;
+        slot.last_used = self.clock;
+        Some(&mut slot.value)
+    }
+
+    /// Look up an entry without changing its recency
+    pub(crate) fn peek(&self, key: &K) -> Option<&V> {
+        self.entries.get(key).map(|slot| &slot.value)
+    }
+
+    /// Store an entry as most recently used, evicting the least recently used one when full
+    pub(crate) fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+
+        if !self.entries.contains_key(&key) && self.entries.len() >= self.capacity {
+            self.evict_lru();
+        }
+
+        self.entries.insert(key, Slot { value, last_used: self.clock });
+    }
+
+    pub(crate) fn remove(&mut self, key: &K) -> Option<V> {
+        self.entries.remove(key).map(|slot| slot.value)
+    }
+
+    /// Keep only the entries whose key satisfies `keep`
+    pub(crate) fn retain_keys(&mut self, mut keep: impl FnMut(&K) -> bool) {
+        self.entries.retain(|key, _| keep(key));
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn evict_lru(&mut self) {
+        let oldest =
+            self.entries.iter().min_by_key(|(_, slot)| slot.last_used).map(|(key, _)| *key);
+
+        if let Some(key) = oldest {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut map = LruMap::with_capacity(2);
+        map.insert(1, "a");
+        map.insert(2, "b");
+        assert!(map.get_mut(&1).is_some());
+        map.insert(3, "c");
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.peek(&1), Some(&"a"));
+        assert_eq!(map.peek(&2), None);
+        assert_eq!(map.peek(&3), Some(&"c"));
+    }
+
+    #[test]
+    fn test_replacing_a_key_does_not_evict() {
+        let mut map = LruMap::with_capacity(2);
+        map.insert(1, "a");
+        map.insert(2, "b");
+        map.insert(2, "B");
+
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.peek(&1), Some(&"a"));
+        assert_eq!(map.peek(&2), Some(&"B"));
+    }
+
+    #[test]
+    fn test_peek_does_not_refresh_recency() {
+        let mut map = LruMap::with_capacity(2);
+        map.insert(1, "a");
+        map.insert(2, "b");
+        assert!(map.peek(&1).is_some());
+        map.insert(3, "c");
+
+        assert_eq!(map.peek(&1), None);
+        assert_eq!(map.peek(&2), Some(&"b"));
+    }
+}
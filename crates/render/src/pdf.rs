@@ -2,11 +2,14 @@
 //!
 //! Provides a high-level interface to PDF documents using PDFium.
 
+use crate::timing::RenderTimings;
 use crate::RenderQuality;
 use pdfium_render::prelude::*;
 use std::cell::OnceCell;
 use std::path::Path;
-use std::sync::{Mutex, OnceLock};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
 use std::{env, fs};
 
 thread_local! {
@@ -71,6 +74,109 @@ pub fn detect_needs_ocr(text: &str) -> bool {
     false
 }
 
+/// Default confidence at or above which a page is reported as needing OCR
+pub const DEFAULT_OCR_CONFIDENCE_THRESHOLD: f32 = 0.5;
+
+/// Score how likely a page is to need OCR
+///
+/// Combines the density of extractable text with how much of the page is
+/// covered by raster images. Scanned pages have little or no text and are
+/// dominated by a single image; born-digital pages have plenty of text.
+///
+/// # Logic
+/// - Text score is the smaller of the char and word ratios against the
+///   `detect_needs_ocr` thresholds, capped at 1.0
+/// - Sufficient text (score 1.0) → 0.0 regardless of images
+/// - Otherwise the missing text fraction is weighted by image coverage,
+///   so an empty page without images scores 0.5 and an empty full-page
+///   scan scores 1.0
+///
+/// # Arguments
+/// * `text` - The extracted text from the page
+/// * `image_coverage` - Fraction of the page area covered by images (0.0-1.0)
+///
+/// # Returns
+/// A confidence in `0.0..=1.0` that the page needs OCR
+pub fn ocr_confidence(text: &str, image_coverage: f32) -> f32 {
+    let char_count = text.chars().filter(|c| !c.is_whitespace()).count();
+    let word_count =
+        text.split_whitespace().filter(|word| word.chars().any(|c| c.is_alphanumeric())).count();
+
+    let char_ratio = (char_count as f32 / MIN_TEXT_CHARS_THRESHOLD as f32).min(1.0);
+    let word_ratio = (word_count as f32 / MIN_WORD_COUNT_THRESHOLD as f32).min(1.0);
+    let text_score = char_ratio.min(word_ratio);
+
+    let coverage = if image_coverage.is_finite() { image_coverage.clamp(0.0, 1.0) } else { 0.0 };
+
+    ((1.0 - text_score) * (0.5 + 0.5 * coverage)).clamp(0.0, 1.0)
+}
+
+/// Resolve a user-typed page label to a zero-based page index
+///
+/// Labels are matched exactly after trimming; roman numeral labels also match
+/// case-insensitively, so "iii" finds "III". When several pages share a label
+/// the first one wins. If nothing matches, the input is parsed as a 1-based
+/// physical page number.
+///
+/// # Arguments
+/// * `labels` - Label for each page in order, `None` for unlabeled pages
+/// * `label` - The label to look up
+///
+/// # Returns
+/// The zero-based page index, or `None` if the label can't be resolved
+pub fn resolve_page_label_in(labels: &[Option<String>], label: &str) -> Option<u16> {
+    let label = label.trim();
+    if label.is_empty() {
+        return None;
+    }
+
+    let is_roman = |text: &str| text.chars().all(|c| "ivxlcdmIVXLCDM".contains(c));
+    let matches = |candidate: &str| {
+        candidate == label
+            || (is_roman(candidate) && is_roman(label) && candidate.eq_ignore_ascii_case(label))
+    };
+
+    if let Some(index) =
+        labels.iter().position(|candidate| candidate.as_deref().map(str::trim).is_some_and(matches))
+    {
+        return u16::try_from(index).ok();
+    }
+
+    let physical = label.parse::<usize>().ok()?;
+    if physical == 0 || physical > labels.len() {
+        return None;
+    }
+
+    u16::try_from(physical - 1).ok()
+}
+
+/// Default cap on the pixel count (width * height) of a single page render
+pub const DEFAULT_MAX_RENDER_PIXELS: u64 = 32_000_000;
+
+/// Reduce a render scale so the rendered page stays within a pixel budget
+///
+/// # Arguments
+/// * `page_width` - Page width in points
+/// * `page_height` - Page height in points
+/// * `scale` - Requested pixels per point
+/// * `max_pixels` - Largest allowed width * height of the rendered bitmap
+///
+/// # Returns
+/// `scale` unchanged if it fits, otherwise the largest scale that does
+pub fn clamp_render_scale(page_width: f32, page_height: f32, scale: f32, max_pixels: u64) -> f32 {
+    let area = page_width as f64 * page_height as f64;
+    if area <= 0.0 || scale <= 0.0 {
+        return scale;
+    }
+
+    let requested = area * scale as f64 * scale as f64;
+    if requested <= max_pixels as f64 {
+        return scale;
+    }
+
+    (max_pixels as f64 / area).sqrt() as f32
+}
+
 /// Errors that can occur during PDF operations
 #[derive(Debug)]
 pub enum PdfError {
@@ -110,6 +216,12 @@ pub type PdfResult<T> = Result<T, PdfError>;
 pub struct PdfDocument {
     /// The loaded PDF document (owns the Pdfium instance internally)
     document: pdfium_render::prelude::PdfDocument<'static>,
+
+    /// Wall-clock durations of scaled page renders
+    render_timings: RenderTimings,
+
+    /// Largest width * height a scaled page render may produce
+    max_render_pixels: u64,
 }
 
 impl PdfDocument {
@@ -346,7 +458,11 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
             .load_pdf_from_file(path.as_ref(), None)
             .map_err(|e| PdfError::LoadError(e.to_string()))?;
 
-        Ok(Self { document })
+        Ok(Self {
+            document,
+            render_timings: RenderTimings::new(),
+            max_render_pixels: DEFAULT_MAX_RENDER_PIXELS,
+        })
     }
 
     /// Load a PDF document from byte data (owned)
@@ -370,7 +486,11 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
             .load_pdf_from_byte_slice(data_static, None)
             .map_err(|e| PdfError::LoadError(e.to_string()))?;
 
-        Ok(Self { document })
+        Ok(Self {
+            document,
+            render_timings: RenderTimings::new(),
+            max_render_pixels: DEFAULT_MAX_RENDER_PIXELS,
+        })
     }
 
     /// Get the number of pages in the document
@@ -402,6 +522,37 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
         Ok(PageDimensions { width: page.width().value, height: page.height().value })
     }
 
+    /// Iterate page dimensions in page order, measuring the rest in the background.
+    ///
+    /// The first page is measured on the calling thread before this returns, so
+    /// callers can lay out and paint page one before the rest of a large document
+    /// has been measured. Once the first item has been taken, a background thread
+    /// measures the remaining pages and the iterator yields them as they arrive.
+    pub fn page_dimensions_lazy(self: &Arc<Self>) -> PageDimensionsIter {
+        let page_count = self.page_count();
+        let first = (page_count > 0).then(|| self.page_dimensions(0));
+        let (start_tx, start_rx) = mpsc::channel::<()>();
+        let (rest_tx, rest_rx) = mpsc::channel();
+
+        if page_count > 1 {
+            let document = Arc::clone(self);
+            thread::spawn(move || {
+                // Wait until page one has been handed out so the two don't contend
+                // for the PDFium lock.
+                if start_rx.recv().is_err() {
+                    return;
+                }
+                for index in 1..page_count {
+                    if rest_tx.send(document.page_dimensions(index)).is_err() {
+                        return;
+                    }
+                }
+            });
+        }
+
+        PageDimensionsIter { first, start: Some(start_tx), rest: rest_rx, remaining: page_count }
+    }
+
     /// Get the document's metadata
     pub fn metadata(&self) -> PdfMetadata {
         let _guard = Self::operation_lock().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
@@ -459,6 +610,88 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
         Ok(!detect_needs_ocr(&text))
     }
 
+    /// Get the page label for every page (e.g. "iii", "A-1", "23")
+    ///
+    /// # Returns
+    /// One entry per page, `None` for pages without a label
+    pub fn page_labels(&self) -> Vec<Option<String>> {
+        let _guard = Self::operation_lock().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        self.document.pages().iter().map(|page| page.label()).collect()
+    }
+
+    /// Resolve a page label such as "iv" or "23" to a zero-based page index
+    ///
+    /// See [`resolve_page_label_in`] for the matching rules.
+    pub fn resolve_page_label(&self, label: &str) -> Option<u16> {
+        resolve_page_label_in(&self.page_labels(), label)
+    }
+
+    /// Fraction of a page's area covered by image objects
+    ///
+    /// Overlapping images are summed, so the result is capped at 1.0.
+    ///
+    /// # Arguments
+    /// * `page_index` - Zero-based page index
+    ///
+    /// # Returns
+    /// Coverage in `0.0..=1.0` or an error if the page index is invalid
+    pub fn page_image_coverage(&self, page_index: u16) -> PdfResult<f32> {
+        let _guard = Self::operation_lock().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let page = self
+            .document
+            .pages()
+            .get(page_index)
+            .map_err(|_| PdfError::InvalidPageIndex(page_index))?;
+
+        let page_area = page.width().value * page.height().value;
+        if page_area <= 0.0 {
+            return Ok(0.0);
+        }
+
+        let image_area: f32 = page
+            .objects()
+            .iter()
+            .filter(|object| object.object_type() == PdfPageObjectType::Image)
+            .filter_map(|object| object.bounds().ok())
+            .map(|bounds| (bounds.width().value * bounds.height().value).abs())
+            .sum();
+
+        Ok((image_area / page_area).clamp(0.0, 1.0))
+    }
+
+    /// Score every page for OCR need
+    ///
+    /// Returns one `ocr_confidence` value per page, in page order. Mixed
+    /// documents can use this to badge only their scanned pages.
+    pub fn ocr_confidence_per_page(&self) -> PdfResult<Vec<f32>> {
+        let page_count = self.page_count();
+        let mut confidences = Vec::with_capacity(page_count as usize);
+
+        for page_index in 0..page_count {
+            let text = self.extract_page_text(page_index)?;
+            let coverage = self.page_image_coverage(page_index)?;
+            confidences.push(ocr_confidence(&text, coverage));
+        }
+
+        Ok(confidences)
+    }
+
+    /// Decide per page whether OCR is needed
+    ///
+    /// # Arguments
+    /// * `threshold` - Confidence at or above which a page needs OCR
+    ///   (see `DEFAULT_OCR_CONFIDENCE_THRESHOLD`)
+    ///
+    /// # Returns
+    /// One flag per page, `true` where the page needs OCR
+    pub fn detect_needs_ocr_per_page(&self, threshold: f32) -> PdfResult<Vec<bool>> {
+        Ok(self
+            .ocr_confidence_per_page()?
+            .into_iter()
+            .map(|confidence| confidence >= threshold)
+            .collect())
+    }
+
     /// Render a page to RGBA pixel data
     ///
     /// # Arguments
@@ -542,6 +775,7 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
         let page_height = dimensions.height;
 
         let scale = (max_width as f32 / page_width).min(max_height as f32 / page_height).max(0.1);
+        let scale = clamp_render_scale(page_width, page_height, scale, self.max_render_pixels);
 
         let render_width = (page_width * scale) as u32;
         let render_height = (page_height * scale) as u32;
@@ -550,11 +784,62 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
         let quality_width = ((render_width as f32) * scale).round().max(1.0) as u32;
         let quality_height = ((render_height as f32) * scale).round().max(1.0) as u32;
 
+        let started = Instant::now();
         let rgba =
             self.render_page_rgba_with_quality(page_index, render_width, render_height, quality)?;
+        self.render_timings.record(page_index, started.elapsed());
+
         Ok((rgba, quality_width, quality_height))
     }
 
+    /// Render a page at a fixed scale, reduced if needed to respect the pixel cap
+    ///
+    /// # Arguments
+    /// * `page_index` - Zero-based page index
+    /// * `scale` - Requested pixels per point (1.0 renders at 72 DPI)
+    ///
+    /// # Returns
+    /// Tuple of (rgba_data, width, height, scale_used) or an error
+    pub fn render_page_at_scale(
+        &self,
+        page_index: u16,
+        scale: f32,
+    ) -> PdfResult<(Vec<u8>, u32, u32, f32)> {
+        if !scale.is_finite() || scale <= 0.0 {
+            return Err(PdfError::RenderError(format!("Invalid render scale: {}", scale)));
+        }
+
+        let dimensions = self.page_dimensions(page_index)?;
+        let scale =
+            clamp_render_scale(dimensions.width, dimensions.height, scale, self.max_render_pixels);
+        let width = ((dimensions.width * scale) as u32).max(1);
+        let height = ((dimensions.height * scale) as u32).max(1);
+
+        let started = Instant::now();
+        let rgba = self.render_page_rgba(page_index, width, height)?;
+        self.render_timings.record(page_index, started.elapsed());
+
+        Ok((rgba, width, height, scale))
+    }
+
+    /// Largest width * height a scaled page render may produce
+    pub fn max_render_pixels(&self) -> u64 {
+        self.max_render_pixels
+    }
+
+    /// Set the pixel cap for scaled page renders (at least one pixel)
+    pub fn set_max_render_pixels(&mut self, max_pixels: u64) {
+        self.max_render_pixels = max_pixels.max(1);
+    }
+
+    /// Get the per-page timings recorded by `render_page_scaled`
+    ///
+    /// Collection is enabled by default only in debug builds; use
+    /// `RenderTimings::set_enabled` to toggle it.
+    pub fn render_timings(&self) -> &RenderTimings {
+        &self.render_timings
+    }
+
     /// Extract text with bounding boxes from a page
     ///
     /// Returns individual text spans with their positions in page coordinates.
@@ -664,6 +949,65 @@ or run scripts/setup_pdfium.sh to install a local runtime copy.",
         Ok(spans)
     }
 
+    /// Extract the raster images placed on a page
+    ///
+    /// JPEG (DCTDecode), Flate-compressed and unfiltered images are decoded to
+    /// RGBA. Images using other filters (JBIG2, JPEG 2000, CCITT fax, ...) or
+    /// that fail to decode are reported in `PageImages::skipped` so one exotic
+    /// image doesn't fail the page.
+    ///
+    /// # Arguments
+    /// * `page_index` - Zero-based page index
+    ///
+    /// # Returns
+    /// The decoded images in page content order, plus the images that were skipped
+    pub fn extract_page_images(&self, page_index: u16) -> PdfResult<PageImages> {
+        let _guard = Self::operation_lock().lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let page = self
+            .document
+            .pages()
+            .get(page_index)
+            .map_err(|_| PdfError::InvalidPageIndex(page_index))?;
+
+        let mut images = Vec::new();
+        let mut skipped = Vec::new();
+        for object in page.objects().iter() {
+            let Some(image) = object.as_image_object() else {
+                continue;
+            };
+
+            // The last filter in the chain determines how the image data is encoded.
+            let filter = image.filters().iter().last().map(|filter| filter.name().to_string());
+            let format = match filter.as_deref() {
+                None => ExtractedImageFormat::Raw,
+                Some("DCTDecode") => ExtractedImageFormat::Jpeg,
+                Some("FlateDecode") => ExtractedImageFormat::Flate,
+                Some(_) => {
+                    skipped.push(SkippedImage { filter, reason: SkipReason::UnsupportedFilter });
+                    continue;
+                }
+            };
+
+            let decoded = match image.get_raw_image() {
+                Ok(decoded) => decoded.to_rgba8(),
+                Err(err) => {
+                    skipped
+                        .push(SkippedImage { filter, reason: SkipReason::Decode(err.to_string()) });
+                    continue;
+                }
+            };
+
+            images.push(ExtractedImage {
+                width: decoded.width(),
+                height: decoded.height(),
+                bytes: decoded.into_raw(),
+                format,
+            });
+        }
+
+        Ok(PageImages { images, skipped })
+    }
+
     /// Save the PDF document to a file
     ///
     /// # Arguments
@@ -728,6 +1072,57 @@ pub struct TextSpanInfo {
     pub height: f32,
 }
 
+/// Encoding an extracted image was stored with in the PDF
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractedImageFormat {
+    /// DCTDecode
+    Jpeg,
+    /// FlateDecode
+    Flate,
+    /// Uncompressed samples
+    Raw,
+}
+
+/// Raster image pulled out of a page
+#[derive(Debug, Clone)]
+pub struct ExtractedImage {
+    /// Decoded pixel data in RGBA format (4 bytes per pixel)
+    pub bytes: Vec<u8>,
+    /// Width in image pixels
+    pub width: u32,
+    /// Height in image pixels
+    pub height: u32,
+    /// How the image was encoded in the PDF
+    pub format: ExtractedImageFormat,
+}
+
+/// Why `PdfDocument::extract_page_images` left an image out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The image is encoded with a filter that isn't decoded (JBIG2, JPEG 2000, ...)
+    UnsupportedFilter,
+    /// The image uses a supported filter but its data failed to decode
+    Decode(String),
+}
+
+/// Image on a page that was not extracted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedImage {
+    /// Last filter in the image's filter chain, if any
+    pub filter: Option<String>,
+    /// Why the image was skipped
+    pub reason: SkipReason,
+}
+
+/// Result of extracting the raster images from a page
+#[derive(Debug, Clone)]
+pub struct PageImages {
+    /// Decoded images in page content order
+    pub images: Vec<ExtractedImage>,
+    /// Images that could not be decoded, in page content order
+    pub skipped: Vec<SkippedImage>,
+}
+
 /// Page dimensions in points (1/72 inch)
 #[derive(Debug, Clone, Copy)]
 pub struct PageDimensions {
@@ -735,10 +1130,138 @@ pub struct PageDimensions {
     pub height: f32,
 }
 
+/// Iterator over page dimensions, returned by [`PdfDocument::page_dimensions_lazy`].
+///
+/// Dropping it early stops the background measurement.
+pub struct PageDimensionsIter {
+    /// Page one, measured on the thread that created the iterator
+    first: Option<PdfResult<PageDimensions>>,
+
+    /// Signals the background thread to start measuring the remaining pages
+    start: Option<mpsc::Sender<()>>,
+
+    /// Remaining pages in order, as the background thread measures them
+    rest: mpsc::Receiver<PdfResult<PageDimensions>>,
+
+    /// Pages not yet yielded
+    remaining: u16,
+}
+
+impl Iterator for PageDimensionsIter {
+    type Item = PdfResult<PageDimensions>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = match self.first.take() {
+            Some(first) => {
+                if let Some(start) = self.start.take() {
+                    let _ = start.send(());
+                }
+                first
+            }
+            None => self.rest.recv().ok()?,
+        };
+
+        self.remaining = self.remaining.saturating_sub(1);
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for PageDimensionsIter {}
+
+/// Open a PDF from the workspace's `tests/fixtures` directory
+#[cfg(test)]
+pub(crate) fn open_fixture(name: &str) -> PdfDocument {
+    let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("../../tests/fixtures")
+        .join(name);
+    PdfDocument::open(path).expect("fixture PDF should open")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_clamp_render_scale() {
+        // Letter at 2x is 1224 x 1584 = ~1.9MP, well under the default cap
+        assert_eq!(clamp_render_scale(612.0, 792.0, 2.0, DEFAULT_MAX_RENDER_PIXELS), 2.0);
+
+        // A 36 x 48 inch poster at 4x would be ~398MP
+        let scale = clamp_render_scale(2592.0, 3456.0, 4.0, DEFAULT_MAX_RENDER_PIXELS);
+        assert!(scale < 4.0);
+        let pixels = (2592.0 * scale) as u64 * (3456.0 * scale) as u64;
+        assert!(pixels <= DEFAULT_MAX_RENDER_PIXELS);
+        assert!(pixels > DEFAULT_MAX_RENDER_PIXELS * 99 / 100);
+    }
+
+    #[test]
+    fn test_render_page_at_scale_respects_pixel_cap() {
+        let mut pdf = open_fixture("medium.pdf");
+        pdf.set_max_render_pixels(10_000);
+
+        let (rgba, width, height, scale) = pdf.render_page_at_scale(0, 4.0).unwrap();
+
+        assert!(scale < 4.0);
+        assert!(width as u64 * height as u64 <= 10_000);
+        assert_eq!(rgba.len(), (width * height * 4) as usize);
+    }
+
+    #[test]
+    fn test_page_dimensions_lazy_yields_first_page_before_measuring_the_rest() {
+        let pdf = Arc::new(open_fixture("mixed-sizes.pdf"));
+
+        let mut dimensions = pdf.page_dimensions_lazy();
+        assert_eq!(dimensions.len(), 3);
+
+        // Holding the PDFium lock keeps the background thread from measuring anything.
+        let guard = PdfDocument::operation_lock().lock().unwrap();
+        let first = dimensions.next().unwrap().unwrap();
+        assert_eq!((first.width, first.height), (612.0, 792.0));
+        assert!(matches!(dimensions.rest.try_recv(), Err(mpsc::TryRecvError::Empty)));
+        drop(guard);
+
+        let rest = dimensions
+            .map(|page| page.map(|page| (page.width, page.height)))
+            .collect::<PdfResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(rest, vec![(792.0, 1224.0), (612.0, 792.0)]);
+    }
+
+    #[test]
+    fn test_extract_page_images_decodes_jpeg() {
+        let pdf = open_fixture("image.pdf");
+
+        let PageImages { images, skipped } = pdf.extract_page_images(0).unwrap();
+
+        assert!(skipped.is_empty());
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].format, ExtractedImageFormat::Jpeg);
+        assert_eq!((images[0].width, images[0].height), (16, 8));
+        assert_eq!(images[0].bytes.len(), 16 * 8 * 4);
+        assert!(matches!(pdf.extract_page_images(1), Err(PdfError::InvalidPageIndex(1))));
+    }
+
+    #[test]
+    fn test_extract_page_images_reports_unsupported_filters() {
+        let pdf = open_fixture("unsupported-image.pdf");
+
+        let PageImages { images, skipped } = pdf.extract_page_images(0).unwrap();
+
+        assert!(images.is_empty());
+        assert_eq!(
+            skipped,
+            vec![SkippedImage {
+                filter: Some("JBIG2Decode".to_string()),
+                reason: SkipReason::UnsupportedFilter,
+            }]
+        );
+    }
+
     #[test]
     fn test_pdf_error_display() {
         let err = PdfError::InvalidPageIndex(5);
@@ -875,6 +1398,109 @@ mod tests {
         assert!(!detect_needs_ocr(content_page));
     }
 
+    #[test]
+    fn test_ocr_confidence_text_page() {
+        // A born-digital text page scores zero whatever its images
+        let text = "This is a document with sufficient text content that should not require OCR processing.";
+        assert_eq!(ocr_confidence(text, 0.0), 0.0);
+        assert_eq!(ocr_confidence(text, 0.4), 0.0);
+        assert!(ocr_confidence(text, 0.4) < DEFAULT_OCR_CONFIDENCE_THRESHOLD);
+    }
+
+    #[test]
+    fn test_ocr_confidence_image_only_page() {
+        // A full-page scan with no text layer is certain to need OCR
+        assert_eq!(ocr_confidence("", 1.0), 1.0);
+
+        // A scan with only a stamped sheet number still needs OCR
+        let sheet_number = ocr_confidence("A1", 0.95);
+        assert!(sheet_number >= DEFAULT_OCR_CONFIDENCE_THRESHOLD);
+        assert!(sheet_number < 1.0);
+    }
+
+    #[test]
+    fn test_ocr_confidence_blank_page_sits_at_default_threshold() {
+        // No text and no images: ambiguous, flagged only at the default threshold
+        let blank = ocr_confidence("", 0.0);
+        assert_eq!(blank, 0.5);
+        assert!(blank >= DEFAULT_OCR_CONFIDENCE_THRESHOLD);
+        assert!(blank < 0.75);
+    }
+
+    #[test]
+    fn test_ocr_confidence_agrees_with_detect_needs_ocr() {
+        let samples = [
+            "",
+            "Page 1 of 50",
+            "one two three four five six seven eight nine",
+            "apple banana cherry dates elder figs grape honey iris jades",
+        ];
+
+        for text in samples {
+            assert_eq!(ocr_confidence(text, 1.0) > 0.0, detect_needs_ocr(text), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_ocr_confidence_clamps_coverage() {
+        assert_eq!(ocr_confidence("", 4.0), 1.0);
+        assert_eq!(ocr_confidence("", -1.0), 0.5);
+        assert_eq!(ocr_confidence("", f32::NAN), 0.5);
+    }
+
+    #[test]
+    fn test_page_image_coverage_fixtures() {
+        assert_eq!(open_fixture("small.pdf").page_image_coverage(0).unwrap(), 0.0);
+
+        // image.pdf draws its 16x8 image at 64x32 points on a Letter page
+        let coverage = open_fixture("image.pdf").page_image_coverage(0).unwrap();
+        assert!((coverage - (64.0 * 32.0) / (612.0 * 792.0)).abs() < 1e-3);
+
+        let coverage = open_fixture("scanned.pdf").page_image_coverage(0).unwrap();
+        assert!(coverage > 0.99);
+
+        let err = open_fixture("small.pdf").page_image_coverage(1).unwrap_err();
+        assert!(matches!(err, PdfError::InvalidPageIndex(1)));
+    }
+
+    #[test]
+    fn test_ocr_per_page_text_page_does_not_need_ocr() {
+        let pdf = open_fixture("small.pdf");
+
+        let confidences = pdf.ocr_confidence_per_page().unwrap();
+        assert_eq!(confidences.len(), 1);
+        assert!(confidences[0] < DEFAULT_OCR_CONFIDENCE_THRESHOLD);
+        assert_eq!(
+            pdf.detect_needs_ocr_per_page(DEFAULT_OCR_CONFIDENCE_THRESHOLD).unwrap(),
+            vec![false]
+        );
+    }
+
+    #[test]
+    fn test_ocr_per_page_image_only_page_needs_ocr() {
+        let pdf = open_fixture("scanned.pdf");
+
+        let confidences = pdf.ocr_confidence_per_page().unwrap();
+        assert_eq!(confidences.len(), 1);
+        assert!(confidences[0] > 0.99);
+        assert_eq!(
+            pdf.detect_needs_ocr_per_page(DEFAULT_OCR_CONFIDENCE_THRESHOLD).unwrap(),
+            vec![true]
+        );
+    }
+
+    #[test]
+    fn test_ocr_per_page_small_image_on_text_page_does_not_need_ocr() {
+        let pdf = open_fixture("image.pdf");
+
+        assert_eq!(
+            pdf.detect_needs_ocr_per_page(DEFAULT_OCR_CONFIDENCE_THRESHOLD).unwrap(),
+            vec![false]
+        );
+        // A threshold of 0.0 flags every page, since confidence is never negative
+        assert_eq!(pdf.detect_needs_ocr_per_page(0.0).unwrap(), vec![true]);
+    }
+
     #[test]
     fn test_executable_path_detection() {
         // Verify that we can get the executable's directory
@@ -973,4 +1599,40 @@ mod tests {
         assert_eq!(candidates.len(), 1);
         assert_eq!(candidates[0], Path::new(path));
     }
+
+    fn front_matter_labels() -> Vec<Option<String>> {
+        ["i", "ii", "iii", "iv", "1", "2", "3", "4"]
+            .iter()
+            .map(|label| Some(label.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_page_label_matches_roman_front_matter() {
+        let labels = front_matter_labels();
+
+        assert_eq!(resolve_page_label_in(&labels, "iii"), Some(2));
+        assert_eq!(resolve_page_label_in(&labels, "IV"), Some(3));
+        assert_eq!(resolve_page_label_in(&labels, " 2 "), Some(5));
+    }
+
+    #[test]
+    fn test_resolve_page_label_falls_back_to_physical_number() {
+        let labels = vec![None; 8];
+
+        assert_eq!(resolve_page_label_in(&labels, "5"), Some(4));
+        assert_eq!(resolve_page_label_in(&labels, "0"), None);
+        assert_eq!(resolve_page_label_in(&labels, "9"), None);
+        assert_eq!(resolve_page_label_in(&labels, "iv"), None);
+        assert_eq!(resolve_page_label_in(&labels, ""), None);
+    }
+
+    #[test]
+    fn test_resolve_page_label_prefers_first_match() {
+        let labels =
+            vec![Some("A-1".to_string()), Some("a-1".to_string()), Some("A-1".to_string())];
+
+        assert_eq!(resolve_page_label_in(&labels, "A-1"), Some(0));
+        assert_eq!(resolve_page_label_in(&labels, "a-1"), Some(1));
+    }
 }
@@ -0,0 +1,62 @@
+//! Pixel format conversions shared between rendering backends
+//!
+//! pdfium produces RGBA buffers while GPU upload paths expect BGRA. Swapping
+//! the red and blue channels is its own inverse, so the same helpers convert
+//! in either direction.
+
+/// Swap the red and blue channels of every pixel in place
+///
+/// # Arguments
+/// * `buf` - Tightly packed 4-byte pixels; the length must be a multiple of 4
+pub fn rgba_to_bgra_in_place(buf: &mut [u8]) {
+    debug_assert!(
+        buf.len().is_multiple_of(4),
+        "pixel buffer length {} is not a multiple of 4",
+        buf.len()
+    );
+
+    for pixel in buf.chunks_exact_mut(4) {
+        pixel.swap(0, 2);
+    }
+}
+
+/// Return a copy of `buf` with the red and blue channels swapped
+///
+/// # Arguments
+/// * `buf` - Tightly packed 4-byte pixels; the length must be a multiple of 4
+///
+/// # Returns
+/// A new buffer in the swapped channel order
+pub fn rgba_to_bgra(buf: &[u8]) -> Vec<u8> {
+    let mut converted = buf.to_vec();
+    rgba_to_bgra_in_place(&mut converted);
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgba_to_bgra_swaps_red_and_blue() {
+        let rgba = [10, 20, 30, 255, 1, 2, 3, 4];
+        assert_eq!(rgba_to_bgra(&rgba), vec![30, 20, 10, 255, 3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn test_rgba_bgra_round_trip() {
+        let original = vec![200, 100, 50, 128];
+        let mut pixels = original.clone();
+
+        rgba_to_bgra_in_place(&mut pixels);
+        assert_eq!(pixels, vec![50, 100, 200, 128]);
+
+        rgba_to_bgra_in_place(&mut pixels);
+        assert_eq!(pixels, original);
+    }
+
+    #[test]
+    fn test_rgba_to_bgra_empty_buffer() {
+        assert!(rgba_to_bgra(&[]).is_empty());
+    }
+}
@@ -73,8 +73,13 @@ impl ProgressiveTileLoader {
         let mut results = Vec::new();
 
         // Stage 1: Render preview tile
-        let preview_id =
-            TileId::new(page_index, coordinate, zoom_level, rotation, TileProfile::Preview);
+        let preview_id = self.renderer.tile_id(
+            page_index,
+            coordinate,
+            zoom_level,
+            rotation,
+            TileProfile::Preview,
+        );
         let preview_tile = self.renderer.render_tile(document, &preview_id)?;
 
         // Update state
@@ -92,7 +97,7 @@ impl ProgressiveTileLoader {
 
         // Stage 2: Render crisp tile
         let crisp_id =
-            TileId::new(page_index, coordinate, zoom_level, rotation, TileProfile::Crisp);
+            self.renderer.tile_id(page_index, coordinate, zoom_level, rotation, TileProfile::Crisp);
         let crisp_tile = self.renderer.render_tile(document, &crisp_id)?;
 
         // Update state
@@ -137,8 +142,13 @@ impl ProgressiveTileLoader {
         for y in 0..rows {
             for x in 0..columns {
                 let coordinate = TileCoordinate::new(x, y);
-                let preview_id =
-                    TileId::new(page_index, coordinate, zoom_level, rotation, TileProfile::Preview);
+                let preview_id = self.renderer.tile_id(
+                    page_index,
+                    coordinate,
+                    zoom_level,
+                    rotation,
+                    TileProfile::Preview,
+                );
 
                 let preview_tile = self.renderer.render_tile(document, &preview_id)?;
 
@@ -161,8 +171,13 @@ impl ProgressiveTileLoader {
         for y in 0..rows {
             for x in 0..columns {
                 let coordinate = TileCoordinate::new(x, y);
-                let crisp_id =
-                    TileId::new(page_index, coordinate, zoom_level, rotation, TileProfile::Crisp);
+                let crisp_id = self.renderer.tile_id(
+                    page_index,
+                    coordinate,
+                    zoom_level,
+                    rotation,
+                    TileProfile::Crisp,
+                );
 
                 let crisp_tile = self.renderer.render_tile(document, &crisp_id)?;
 
@@ -0,0 +1,245 @@
+//! Two-layer page render cache
+//!
+//! Annotation edits change what is drawn on top of a page but not the page
+//! itself. The cache keeps the base page bitmap keyed without the annotation
+//! generation, so a generation bump only re-runs the overlay composite.
+
+use crate::lru::LruMap;
+use crate::pdf::PdfResult;
+use std::sync::Arc;
+
+/// Identity of a composited page render
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RenderCacheKey {
+    /// Caller-assigned document identifier
+    pub document_id: u64,
+
+    /// Page index (0-based)
+    pub page_index: u16,
+
+    /// Zoom level (represented as percentage, e.g., 100 = 100%)
+    pub zoom: u32,
+
+    /// Generation of the page's annotations; bump it when they change
+    pub annotation_generation: u64,
+}
+
+impl RenderCacheKey {
+    /// Create a new render cache key
+    pub fn new(document_id: u64, page_index: u16, zoom: u32, annotation_generation: u64) -> Self {
+        Self { document_id, page_index, zoom, annotation_generation }
+    }
+
+    fn base(&self) -> BaseKey {
+        BaseKey { document_id: self.document_id, page_index: self.page_index, zoom: self.zoom }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct BaseKey {
+    document_id: u64,
+    page_index: u16,
+    zoom: u32,
+}
+
+/// Default number of page/zoom slots kept before the least recently used is evicted
+pub const DEFAULT_RENDER_CACHE_CAPACITY: usize = 64;
+
+#[derive(Debug)]
+struct CompositedEntry {
+    annotation_generation: u64,
+    pixels: Arc<Vec<u8>>,
+}
+
+#[derive(Debug)]
+struct PageEntry {
+    base: Arc<Vec<u8>>,
+    composited: Option<CompositedEntry>,
+}
+
+/// LRU cache of page bitmaps and their annotation composites
+///
+/// Each page and zoom occupies one slot holding its base bitmap and newest
+/// composite; older generations are replaced as soon as a newer one is
+/// rendered. Evicting a slot drops both layers.
+#[derive(Debug)]
+pub struct RenderCache {
+    entries: LruMap<BaseKey, PageEntry>,
+}
+
+impl RenderCache {
+    /// Create a cache holding up to `DEFAULT_RENDER_CACHE_CAPACITY` page/zoom slots
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_RENDER_CACHE_CAPACITY)
+    }
+
+    /// Create a cache holding up to `capacity` page/zoom slots (at least one)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { entries: LruMap::with_capacity(capacity) }
+    }
+
+    /// Return the composited render for `key`, rendering whatever layers are missing
+    ///
+    /// # Arguments
+    /// * `key` - Page, zoom and annotation generation to render
+    /// * `render_base` - Renders the page without annotations; only called when
+    ///   no base bitmap is cached for this page and zoom
+    /// * `composite` - Draws the annotations over a copy of the base bitmap
+    ///
+    /// # Returns
+    /// RGBA pixel data of the page with annotations applied
+    pub fn get_or_render<B, C>(
+        &mut self,
+        key: RenderCacheKey,
+        render_base: B,
+        composite: C,
+    ) -> PdfResult<Arc<Vec<u8>>>
+    where
+        B: FnOnce() -> PdfResult<Vec<u8>>,
+        C: FnOnce(&[u8]) -> PdfResult<Vec<u8>>,
+    {
+        let base_key = key.base();
+
+        let base = match self.entries.get_mut(&base_key) {
+            Some(entry) => {
+                if let Some(composited) = &entry.composited {
+                    if composited.annotation_generation == key.annotation_generation {
+                        return Ok(Arc::clone(&composited.pixels));
+                    }
+                }
+                Arc::clone(&entry.base)
+            }
+            None => {
+                let base = Arc::new(render_base()?);
+                self.entries
+                    .insert(base_key, PageEntry { base: Arc::clone(&base), composited: None });
+                base
+            }
+        };
+
+        let pixels = Arc::new(composite(&base)?);
+        if let Some(entry) = self.entries.get_mut(&base_key) {
+            entry.composited = Some(CompositedEntry {
+                annotation_generation: key.annotation_generation,
+                pixels: Arc::clone(&pixels),
+            });
+        }
+
+        Ok(pixels)
+    }
+
+    /// Cached base bitmap for a page at a zoom level, if any
+    pub fn base_page(&self, document_id: u64, page_index: u16, zoom: u32) -> Option<Arc<Vec<u8>>> {
+        self.entries
+            .peek(&BaseKey { document_id, page_index, zoom })
+            .map(|entry| Arc::clone(&entry.base))
+    }
+
+    /// Drop every cached layer belonging to a document
+    pub fn remove_document(&mut self, document_id: u64) {
+        self.entries.retain_keys(|key| key.document_id != document_id);
+    }
+
+    /// Number of cached base bitmaps
+    pub fn base_len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for RenderCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn white_page() -> PdfResult<Vec<u8>> {
+        Ok(vec![255; 4 * 4 * 4])
+    }
+
+    /// Marks the first pixel with the generation so composites are distinguishable
+    fn overlay(generation: u8) -> impl FnOnce(&[u8]) -> PdfResult<Vec<u8>> {
+        move |base| {
+            let mut pixels = base.to_vec();
+            pixels[0] = generation;
+            Ok(pixels)
+        }
+    }
+
+    #[test]
+    fn test_generation_bump_reuses_base_page() {
+        let mut cache = RenderCache::new();
+        let base_renders = Cell::new(0);
+        let render_base = || {
+            base_renders.set(base_renders.get() + 1);
+            white_page()
+        };
+
+        let first = cache.get_or_render(RenderCacheKey::new(1, 0, 100, 0), render_base, overlay(0));
+        let second =
+            cache.get_or_render(RenderCacheKey::new(1, 0, 100, 1), render_base, overlay(1));
+
+        assert_eq!(base_renders.get(), 1);
+        assert_eq!(first.unwrap()[0], 0);
+        assert_eq!(second.unwrap()[0], 1);
+        assert_eq!(cache.base_page(1, 0, 100).unwrap()[0], 255);
+    }
+
+    #[test]
+    fn test_same_generation_hits_composited_result() {
+        let mut cache = RenderCache::new();
+        let key = RenderCacheKey::new(1, 0, 100, 3);
+
+        let first = cache.get_or_render(key, white_page, overlay(3)).unwrap();
+        let second = cache
+            .get_or_render(key, || panic!("base should be cached"), |_| panic!("overlay cached"))
+            .unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn test_zoom_is_part_of_the_base_key() {
+        let mut cache = RenderCache::new();
+
+        cache.get_or_render(RenderCacheKey::new(1, 0, 100, 0), white_page, overlay(0)).unwrap();
+        cache.get_or_render(RenderCacheKey::new(1, 0, 200, 0), white_page, overlay(0)).unwrap();
+
+        assert_eq!(cache.base_len(), 2);
+    }
+
+    #[test]
+    fn test_least_recently_used_page_is_evicted() {
+        let mut cache = RenderCache::with_capacity(2);
+
+        cache.get_or_render(RenderCacheKey::new(1, 0, 100, 0), white_page, overlay(0)).unwrap();
+        cache.get_or_render(RenderCacheKey::new(1, 1, 100, 0), white_page, overlay(0)).unwrap();
+        // Touch page 0 so page 1 becomes the least recently used slot.
+        cache.get_or_render(RenderCacheKey::new(1, 0, 100, 1), white_page, overlay(1)).unwrap();
+        cache.get_or_render(RenderCacheKey::new(1, 2, 100, 0), white_page, overlay(0)).unwrap();
+
+        assert_eq!(cache.base_len(), 2);
+        assert!(cache.base_page(1, 0, 100).is_some());
+        assert!(cache.base_page(1, 1, 100).is_none());
+        assert!(cache.base_page(1, 2, 100).is_some());
+    }
+
+    #[test]
+    fn test_remove_document() {
+        let mut cache = RenderCache::new();
+
+        cache.get_or_render(RenderCacheKey::new(1, 0, 100, 0), white_page, overlay(0)).unwrap();
+        cache.remove_document(1);
+
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,241 @@
+//! Page thumbnail cache
+//!
+//! Keeps rendered thumbnails around so re-showing a thumbnail strip doesn't
+//! re-rasterize every page. Entries carry the page's content generation; when
+//! a page's annotations change the caller bumps the generation and the stale
+//! thumbnail is treated as a miss.
+
+use crate::lru::LruMap;
+use crate::pdf::PdfResult;
+use std::sync::Arc;
+
+/// Default number of thumbnails kept before the least recently used is evicted
+pub const DEFAULT_THUMBNAIL_CACHE_CAPACITY: usize = 512;
+
+/// Identity of a thumbnail: which page, rendered at which size
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThumbnailKey {
+    /// Caller-assigned document identifier
+    pub document_id: u64,
+
+    /// Page index (0-based)
+    pub page_index: u16,
+
+    /// Target width in pixels
+    pub width: u32,
+
+    /// Target height in pixels
+    pub height: u32,
+}
+
+impl ThumbnailKey {
+    /// Create a new thumbnail key
+    pub fn new(document_id: u64, page_index: u16, width: u32, height: u32) -> Self {
+        Self { document_id, page_index, width, height }
+    }
+}
+
+/// Rendered thumbnail pixels
+#[derive(Debug, Clone)]
+pub struct CachedThumbnail {
+    /// Pixel data in RGBA format (4 bytes per pixel)
+    pub pixels: Arc<Vec<u8>>,
+
+    /// Width of the thumbnail in pixels
+    pub width: u32,
+
+    /// Height of the thumbnail in pixels
+    pub height: u32,
+}
+
+#[derive(Debug)]
+struct ThumbnailEntry {
+    thumbnail: CachedThumbnail,
+    generation: u64,
+}
+
+/// LRU cache of page thumbnails keyed by `ThumbnailKey`
+#[derive(Debug)]
+pub struct ThumbnailCache {
+    entries: LruMap<ThumbnailKey, ThumbnailEntry>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ThumbnailCache {
+    /// Create a cache holding up to `DEFAULT_THUMBNAIL_CACHE_CAPACITY` thumbnails
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_THUMBNAIL_CACHE_CAPACITY)
+    }
+
+    /// Create a cache holding up to `capacity` thumbnails (at least one)
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { entries: LruMap::with_capacity(capacity), hits: 0, misses: 0 }
+    }
+
+    /// Look up a thumbnail rendered at content generation `generation`
+    ///
+    /// An entry from a different generation is dropped and reported as a miss.
+    pub fn get(&mut self, key: &ThumbnailKey, generation: u64) -> Option<CachedThumbnail> {
+        match self.entries.get_mut(key) {
+            Some(entry) if entry.generation == generation => {
+                self.hits += 1;
+                Some(entry.thumbnail.clone())
+            }
+            Some(_) => {
+                self.entries.remove(key);
+                self.misses += 1;
+                None
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store a thumbnail, evicting the least recently used entry when full
+    pub fn insert(&mut self, key: ThumbnailKey, generation: u64, thumbnail: CachedThumbnail) {
+        self.entries.insert(key, ThumbnailEntry { thumbnail, generation });
+    }
+
+    /// Return the cached thumbnail or render, cache and return a new one
+    ///
+    /// # Arguments
+    /// * `key` - Thumbnail identity
+    /// * `generation` - Current content generation of the page
+    /// * `render` - Produces RGBA pixels at `key.width` x `key.height` on a miss
+    pub fn get_or_render<F>(
+        &mut self,
+        key: ThumbnailKey,
+        generation: u64,
+        render: F,
+    ) -> PdfResult<CachedThumbnail>
+    where
+        F: FnOnce() -> PdfResult<Vec<u8>>,
+    {
+        if let Some(thumbnail) = self.get(&key, generation) {
+            return Ok(thumbnail);
+        }
+
+        let thumbnail =
+            CachedThumbnail { pixels: Arc::new(render()?), width: key.width, height: key.height };
+        self.insert(key, generation, thumbnail.clone());
+        Ok(thumbnail)
+    }
+
+    /// Drop every thumbnail belonging to a document
+    pub fn remove_document(&mut self, document_id: u64) {
+        self.entries.retain_keys(|key| key.document_id != document_id);
+    }
+
+    /// Number of cached thumbnails
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Number of lookups served from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups that found no usable entry
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+impl Default for ThumbnailCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_solid(key: &ThumbnailKey) -> PdfResult<Vec<u8>> {
+        Ok(vec![255; (key.width * key.height * 4) as usize])
+    }
+
+    #[test]
+    fn test_repeated_request_hits_cache() {
+        let mut cache = ThumbnailCache::new();
+        let key = ThumbnailKey::new(1, 0, 8, 10);
+        let mut renders = 0;
+
+        for _ in 0..2 {
+            let thumbnail = cache
+                .get_or_render(key, 0, || {
+                    renders += 1;
+                    render_solid(&key)
+                })
+                .unwrap();
+            assert_eq!(thumbnail.pixels.len(), 8 * 10 * 4);
+        }
+
+        assert_eq!(renders, 1);
+        assert_eq!(cache.hits(), 1);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn test_generation_bump_forces_miss() {
+        let mut cache = ThumbnailCache::new();
+        let key = ThumbnailKey::new(1, 3, 4, 4);
+
+        cache.get_or_render(key, 0, || render_solid(&key)).unwrap();
+        assert!(cache.get(&key, 1).is_none());
+        assert!(cache.is_empty());
+
+        cache.get_or_render(key, 1, || render_solid(&key)).unwrap();
+        assert!(cache.get(&key, 1).is_some());
+    }
+
+    #[test]
+    fn test_size_is_part_of_the_key() {
+        let mut cache = ThumbnailCache::new();
+        let small = ThumbnailKey::new(1, 0, 4, 4);
+        let large = ThumbnailKey::new(1, 0, 8, 8);
+
+        cache.get_or_render(small, 0, || render_solid(&small)).unwrap();
+        assert!(cache.get(&large, 0).is_none());
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = ThumbnailCache::with_capacity(2);
+        let first = ThumbnailKey::new(1, 0, 4, 4);
+        let second = ThumbnailKey::new(1, 1, 4, 4);
+        let third = ThumbnailKey::new(1, 2, 4, 4);
+
+        cache.get_or_render(first, 0, || render_solid(&first)).unwrap();
+        cache.get_or_render(second, 0, || render_solid(&second)).unwrap();
+        assert!(cache.get(&first, 0).is_some());
+        cache.get_or_render(third, 0, || render_solid(&third)).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&first, 0).is_some());
+        assert!(cache.get(&second, 0).is_none());
+    }
+
+    #[test]
+    fn test_remove_document() {
+        let mut cache = ThumbnailCache::new();
+        let doc_a = ThumbnailKey::new(1, 0, 4, 4);
+        let doc_b = ThumbnailKey::new(2, 0, 4, 4);
+
+        cache.get_or_render(doc_a, 0, || render_solid(&doc_a)).unwrap();
+        cache.get_or_render(doc_b, 0, || render_solid(&doc_b)).unwrap();
+        cache.remove_document(1);
+
+        assert!(cache.get(&doc_a, 0).is_none());
+        assert!(cache.get(&doc_b, 0).is_some());
+    }
+}
@@ -5,9 +5,15 @@
 use crate::pdf::{PdfDocument, PdfError, PdfResult};
 use std::hash::{Hash, Hasher};
 
-/// Fixed tile size in pixels (256x256)
+/// Default tile size in pixels (256x256)
 pub const TILE_SIZE: u32 = 256;
 
+/// Smallest tile size accepted by `TileRenderer::with_tile_size`
+pub const MIN_TILE_SIZE: u32 = 64;
+
+/// Largest tile size accepted by `TileRenderer::with_tile_size`
+pub const MAX_TILE_SIZE: u32 = 2048;
+
 /// Tile coordinates within a page
 ///
 /// Represents the position of a tile in the page's tile grid.
@@ -28,6 +34,45 @@ impl TileCoordinate {
     pub fn to_pixel_offset(&self, tile_size: u32) -> (u32, u32) {
         (self.x * tile_size, self.y * tile_size)
     }
+
+    /// Find the tile containing a pixel position
+    pub fn from_pixel(x: u32, y: u32, tile_size: u32) -> Self {
+        let tile_size = tile_size.max(1);
+        Self { x: x / tile_size, y: y / tile_size }
+    }
+
+    /// Iterate the tiles that intersect a page-space rectangle, row by row
+    ///
+    /// # Arguments
+    /// * `rect` - Rectangle in page points (x, y, width, height), origin top-left
+    /// * `tile_size` - Edge length of the tile grid in pixels
+    /// * `zoom_level` - Zoom percentage (100 = 100%)
+    ///
+    /// # Returns
+    /// An iterator over every intersecting tile; empty if the rectangle has no area
+    pub fn covering(
+        rect: (f32, f32, f32, f32),
+        tile_size: u32,
+        zoom_level: u32,
+    ) -> impl Iterator<Item = TileCoordinate> {
+        let (x, y, width, height) = rect;
+        let tile_size = tile_size.max(1) as f32;
+        let scale = zoom_level as f32 / 100.0;
+
+        let span = |start: f32, length: f32| {
+            if !(length > 0.0 && scale > 0.0) {
+                return 0..0;
+            }
+            let first = (start.max(0.0) * scale / tile_size).floor() as u32;
+            let end = ((start + length).max(0.0) * scale / tile_size).ceil() as u32;
+            first..end
+        };
+
+        let columns = span(x, width);
+        let rows = span(y, height);
+
+        rows.flat_map(move |row| columns.clone().map(move |column| Self::new(column, row)))
+    }
 }
 
 /// Tile identity and metadata
@@ -50,6 +95,9 @@ pub struct TileId {
 
     /// Render profile ("preview" or "crisp")
     pub profile: TileProfile,
+
+    /// Edge length in pixels of the tile grid this coordinate belongs to
+    pub tile_size: u32,
 }
 
 impl TileId {
@@ -61,7 +109,13 @@ impl TileId {
         rotation: u16,
         profile: TileProfile,
     ) -> Self {
-        Self { page_index, coordinate, zoom_level, rotation, profile }
+        Self { page_index, coordinate, zoom_level, rotation, profile, tile_size: TILE_SIZE }
+    }
+
+    /// Use a non-default tile grid for this ID
+    pub fn with_tile_size(mut self, tile_size: u32) -> Self {
+        self.tile_size = tile_size;
+        self
     }
 
     /// Compute a simple hash for this tile ID (for cache keys)
@@ -79,6 +133,7 @@ impl Hash for TileId {
         self.zoom_level.hash(state);
         self.rotation.hash(state);
         self.profile.hash(state);
+        self.tile_size.hash(state);
     }
 }
 
@@ -92,6 +147,54 @@ pub enum TileProfile {
     Crisp,
 }
 
+/// On-screen scroll speed (pixels per second) above which tiles switch to `Preview`
+pub const PREVIEW_SCROLL_VELOCITY: f32 = 2000.0;
+
+/// On-screen scroll speed (pixels per second) below which tiles return to `Crisp`
+///
+/// Kept below `PREVIEW_SCROLL_VELOCITY` so speeds hovering around the switch
+/// point don't flip the profile on every frame.
+pub const CRISP_SCROLL_VELOCITY: f32 = 1200.0;
+
+/// Choose a tile profile for the current scroll speed
+///
+/// Fast scrolling renders `Preview` tiles since they are likely to leave the
+/// viewport before a crisp render would finish. Between the two thresholds the
+/// `current` profile is kept.
+///
+/// # Arguments
+/// * `scroll_velocity` - Scroll speed in page points per second (sign is ignored)
+/// * `zoom_level` - Zoom level as a percentage, used to convert to on-screen speed
+/// * `current` - Profile currently in use
+///
+/// # Returns
+/// The profile to render with
+pub fn select_profile(scroll_velocity: f32, zoom_level: u32, current: TileProfile) -> TileProfile {
+    if !scroll_velocity.is_finite() {
+        return current;
+    }
+
+    let screen_velocity = scroll_velocity.abs() * zoom_level as f32 / 100.0;
+    if screen_velocity > PREVIEW_SCROLL_VELOCITY {
+        TileProfile::Preview
+    } else if screen_velocity < CRISP_SCROLL_VELOCITY {
+        TileProfile::Crisp
+    } else {
+        current
+    }
+}
+
+/// Color space of rendered pixel data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorSpace {
+    /// sRGB-encoded values, as produced by PDFium
+    #[default]
+    Srgb,
+
+    /// Linear-light values
+    Linear,
+}
+
 /// Rendered tile data
 ///
 /// Contains the raw RGBA pixel data for a rendered tile.
@@ -103,11 +206,14 @@ pub struct RenderedTile {
     /// Pixel data in RGBA format (4 bytes per pixel)
     pub pixels: Vec<u8>,
 
-    /// Actual width of the tile in pixels (may be smaller than TILE_SIZE at edges)
+    /// Actual width of the tile in pixels (may be smaller than the tile size at edges)
     pub width: u32,
 
-    /// Actual height of the tile in pixels (may be smaller than TILE_SIZE at edges)
+    /// Actual height of the tile in pixels (may be smaller than the tile size at edges)
     pub height: u32,
+
+    /// Encoding of the color channels in `pixels`
+    pub color_space: ColorSpace,
 }
 
 impl RenderedTile {
@@ -121,6 +227,45 @@ impl RenderedTile {
         // Check if all alpha values are 255
         self.pixels.chunks_exact(4).all(|rgba| rgba[3] == 255)
     }
+
+    /// Convert the tile's color channels to linear light
+    ///
+    /// Applies the sRGB transfer function to R, G and B; alpha is unchanged.
+    /// A tile that is already linear is returned as-is.
+    pub fn to_linear(&self) -> RenderedTile {
+        if self.color_space == ColorSpace::Linear {
+            return self.clone();
+        }
+
+        let mut lut = [0u8; 256];
+        for (value, slot) in lut.iter_mut().enumerate() {
+            *slot = (srgb_to_linear(value as f32 / 255.0) * 255.0).round() as u8;
+        }
+
+        let mut pixels = self.pixels.clone();
+        for rgba in pixels.chunks_exact_mut(4) {
+            for channel in &mut rgba[..3] {
+                *channel = lut[*channel as usize];
+            }
+        }
+
+        RenderedTile {
+            id: self.id.clone(),
+            pixels,
+            width: self.width,
+            height: self.height,
+            color_space: ColorSpace::Linear,
+        }
+    }
+}
+
+/// sRGB electro-optical transfer function for a normalized channel value
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
 }
 
 /// Tile renderer
@@ -138,8 +283,11 @@ impl TileRenderer {
     }
 
     /// Create a new tile renderer with a custom tile size
+    ///
+    /// The size is clamped to `MIN_TILE_SIZE..=MAX_TILE_SIZE` and rounded up
+    /// to the next power of two, which GPUs handle best for texture uploads.
     pub fn with_tile_size(tile_size: u32) -> Self {
-        Self { tile_size }
+        Self { tile_size: tile_size.clamp(MIN_TILE_SIZE, MAX_TILE_SIZE).next_power_of_two() }
     }
 
     /// Get the tile size
@@ -147,6 +295,19 @@ impl TileRenderer {
         self.tile_size
     }
 
+    /// Create a tile ID on this renderer's tile grid
+    pub fn tile_id(
+        &self,
+        page_index: u16,
+        coordinate: TileCoordinate,
+        zoom_level: u32,
+        rotation: u16,
+        profile: TileProfile,
+    ) -> TileId {
+        TileId::new(page_index, coordinate, zoom_level, rotation, profile)
+            .with_tile_size(self.tile_size)
+    }
+
     /// Calculate the tile grid dimensions for a page
     ///
     /// Returns (columns, rows) - the number of tiles in each dimension.
@@ -186,10 +347,11 @@ impl TileRenderer {
         let render_width = (page_width * zoom_factor) as u32;
         let render_height = (page_height * zoom_factor) as u32;
 
-        // Calculate tile position and size
-        let (tile_x, tile_y) = tile_id.coordinate.to_pixel_offset(self.tile_size);
-        let tile_width = self.tile_size.min(render_width.saturating_sub(tile_x));
-        let tile_height = self.tile_size.min(render_height.saturating_sub(tile_y));
+        // Calculate tile position and size on the grid the ID was created for
+        let tile_size = tile_id.tile_size.max(1);
+        let (tile_x, tile_y) = tile_id.coordinate.to_pixel_offset(tile_size);
+        let tile_width = tile_size.min(render_width.saturating_sub(tile_x));
+        let tile_height = tile_size.min(render_height.saturating_sub(tile_y));
 
         // Ensure tile is within bounds
         if tile_width == 0 || tile_height == 0 {
@@ -227,6 +389,7 @@ impl TileRenderer {
             pixels: tile_pixels,
             width: tile_width,
             height: tile_height,
+            color_space: ColorSpace::Srgb,
         })
     }
 
@@ -253,7 +416,7 @@ impl TileRenderer {
 
         for y in 0..rows {
             for x in 0..columns {
-                let tile_id = TileId::new(
+                let tile_id = self.tile_id(
                     page_index,
                     TileCoordinate::new(x, y),
                     zoom_level,
@@ -291,6 +454,29 @@ mod tests {
         assert_eq!(py, 768);
     }
 
+    #[test]
+    fn test_tile_coordinate_covering() {
+        // 640 px is 2.5 tiles of 256, so three columns and three rows
+        let tiles: Vec<_> = TileCoordinate::covering((0.0, 0.0, 640.0, 640.0), 256, 100).collect();
+        let expected: Vec<_> =
+            (0..3).flat_map(|y| (0..3).map(move |x| TileCoordinate::new(x, y))).collect();
+        assert_eq!(tiles, expected);
+
+        // The same rectangle at 200% covers 1280 px, five tiles per axis
+        assert_eq!(TileCoordinate::covering((0.0, 0.0, 640.0, 640.0), 256, 200).count(), 25);
+
+        // An offset rectangle starts in a later tile
+        let tiles: Vec<_> = TileCoordinate::covering((300.0, 10.0, 10.0, 10.0), 256, 100).collect();
+        assert_eq!(tiles, vec![TileCoordinate::new(1, 0)]);
+    }
+
+    #[test]
+    fn test_tile_coordinate_covering_empty_rect() {
+        assert_eq!(TileCoordinate::covering((10.0, 10.0, 0.0, 100.0), 256, 100).count(), 0);
+        assert_eq!(TileCoordinate::covering((10.0, 10.0, 100.0, 0.0), 256, 100).count(), 0);
+        assert_eq!(TileCoordinate::covering((10.0, 10.0, 100.0, 100.0), 256, 0).count(), 0);
+    }
+
     #[test]
     fn test_tile_id_cache_key() {
         let id1 = TileId::new(0, TileCoordinate::new(1, 2), 100, 0, TileProfile::Preview);
@@ -332,6 +518,56 @@ mod tests {
         assert_eq!(rows, 7);
     }
 
+    #[test]
+    fn test_tile_size_is_validated() {
+        assert_eq!(TileRenderer::with_tile_size(1).tile_size(), MIN_TILE_SIZE);
+        assert_eq!(TileRenderer::with_tile_size(100_000).tile_size(), MAX_TILE_SIZE);
+        assert_eq!(TileRenderer::with_tile_size(300).tile_size(), 512);
+        assert_eq!(TileRenderer::with_tile_size(256).tile_size(), 256);
+    }
+
+    #[test]
+    fn test_pixel_mapping_is_consistent_across_tile_sizes() {
+        for tile_size in [256, 512] {
+            let coord = TileCoordinate::new(3, 5);
+            let (px, py) = coord.to_pixel_offset(tile_size);
+            assert_eq!((px, py), (3 * tile_size, 5 * tile_size));
+
+            // Any pixel inside the tile maps back to the same coordinate
+            assert_eq!(TileCoordinate::from_pixel(px, py, tile_size), coord);
+            assert_eq!(
+                TileCoordinate::from_pixel(px + tile_size - 1, py + tile_size - 1, tile_size),
+                coord
+            );
+            assert_eq!(
+                TileCoordinate::from_pixel(px + tile_size, py, tile_size),
+                TileCoordinate::new(4, 5)
+            );
+        }
+
+        // The same pixel lands in different tiles on different grids
+        assert_eq!(TileCoordinate::from_pixel(600, 600, 256), TileCoordinate::new(2, 2));
+        assert_eq!(TileCoordinate::from_pixel(600, 600, 512), TileCoordinate::new(1, 1));
+    }
+
+    #[test]
+    fn test_tile_id_cache_key_includes_tile_size() {
+        let small = TileRenderer::with_tile_size(256);
+        let large = TileRenderer::with_tile_size(512);
+
+        let small_id = small.tile_id(0, TileCoordinate::new(1, 1), 100, 0, TileProfile::Crisp);
+        let large_id = large.tile_id(0, TileCoordinate::new(1, 1), 100, 0, TileProfile::Crisp);
+
+        assert_eq!(small_id.tile_size, 256);
+        assert_eq!(large_id.tile_size, 512);
+        assert_ne!(small_id, large_id);
+        assert_ne!(small_id.cache_key(), large_id.cache_key());
+
+        // IDs built without a renderer use the default grid
+        let default_id = TileId::new(0, TileCoordinate::new(1, 1), 100, 0, TileProfile::Crisp);
+        assert_eq!(default_id.cache_key(), small_id.cache_key());
+    }
+
     #[test]
     fn test_tile_profile_equality() {
         assert_eq!(TileProfile::Preview, TileProfile::Preview);
@@ -339,6 +575,38 @@ mod tests {
         assert_ne!(TileProfile::Preview, TileProfile::Crisp);
     }
 
+    #[test]
+    fn test_select_profile_thresholds() {
+        assert_eq!(select_profile(0.0, 100, TileProfile::Preview), TileProfile::Crisp);
+        assert_eq!(select_profile(3000.0, 100, TileProfile::Crisp), TileProfile::Preview);
+        assert_eq!(select_profile(-3000.0, 100, TileProfile::Crisp), TileProfile::Preview);
+
+        // The same document speed is faster on screen at higher zoom
+        assert_eq!(select_profile(1500.0, 100, TileProfile::Crisp), TileProfile::Crisp);
+        assert_eq!(select_profile(1500.0, 200, TileProfile::Crisp), TileProfile::Preview);
+    }
+
+    #[test]
+    fn test_select_profile_hysteresis_prevents_flapping() {
+        let mut profile = TileProfile::Crisp;
+        let mut changes = 0;
+
+        // Jitter around the preview threshold after crossing it once
+        for velocity in [2100.0, 1900.0, 2050.0, 1800.0, 2010.0, 1500.0] {
+            let next = select_profile(velocity, 100, profile);
+            if next != profile {
+                changes += 1;
+            }
+            profile = next;
+        }
+
+        assert_eq!(profile, TileProfile::Preview);
+        assert_eq!(changes, 1);
+
+        assert_eq!(select_profile(1100.0, 100, profile), TileProfile::Crisp);
+        assert_eq!(select_profile(1500.0, 100, TileProfile::Crisp), TileProfile::Crisp);
+    }
+
     #[test]
     fn test_rendered_tile_byte_size() {
         let tile = RenderedTile {
@@ -346,6 +614,7 @@ mod tests {
             pixels: vec![255u8; 256 * 256 * 4],
             width: 256,
             height: 256,
+            color_space: ColorSpace::Srgb,
         };
 
         assert_eq!(tile.byte_size(), 256 * 256 * 4);
@@ -359,6 +628,7 @@ mod tests {
             pixels: vec![255u8; 256 * 256 * 4],
             width: 256,
             height: 256,
+            color_space: ColorSpace::Srgb,
         };
         assert!(opaque_tile.is_opaque());
 
@@ -370,10 +640,34 @@ mod tests {
             pixels: transparent_pixels,
             width: 256,
             height: 256,
+            color_space: ColorSpace::Srgb,
         };
         assert!(!transparent_tile.is_opaque());
     }
 
+    #[test]
+    fn test_rendered_tile_color_space() {
+        assert_eq!(ColorSpace::default(), ColorSpace::Srgb);
+
+        let tile = RenderedTile {
+            id: TileId::new(0, TileCoordinate::new(0, 0), 100, 0, TileProfile::Preview),
+            pixels: vec![128, 128, 128, 200, 0, 255, 10, 255],
+            width: 2,
+            height: 1,
+            color_space: ColorSpace::Srgb,
+        };
+
+        let linear = tile.to_linear();
+        assert_eq!(linear.color_space, ColorSpace::Linear);
+        // sRGB 128 is ~21.6% linear light: 0.2158 * 255 = 55
+        assert_eq!(&linear.pixels[..4], &[55, 55, 55, 200]);
+        // Black and white are fixed points; 10 falls in the linear segment
+        assert_eq!(&linear.pixels[4..], &[0, 255, 1, 255]);
+
+        // Converting twice is a no-op
+        assert_eq!(linear.to_linear().pixels, linear.pixels);
+    }
+
     // ============================================================================
     // Large PDF Handling Tests (Phase 4.2)
     // ============================================================================
@@ -582,6 +876,7 @@ mod tests {
             pixels: vec![0u8; 256 * 256 * 4],
             width: 256,
             height: 256,
+            color_space: ColorSpace::Srgb,
         };
 
         let reported_size = tile.byte_size();
@@ -0,0 +1,191 @@
+//! Per-page render timing collection
+//!
+//! Records how long each page takes to render so slow pages can be spotted.
+//! Collection is off by default in release builds; when disabled, recording
+//! is a single atomic load.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Most recent renders kept per page; older samples are dropped first
+pub const MAX_SAMPLES_PER_PAGE: usize = 64;
+
+/// Accumulated render durations keyed by page index
+///
+/// Each page keeps at most `MAX_SAMPLES_PER_PAGE` samples, so a page that is
+/// re-rendered for the whole session does not grow memory without bound.
+#[derive(Debug)]
+pub struct RenderTimings {
+    enabled: AtomicBool,
+    samples: Mutex<HashMap<u16, VecDeque<Duration>>>,
+}
+
+impl RenderTimings {
+    /// Create an accumulator, enabled in debug builds and disabled in release builds
+    pub fn new() -> Self {
+        Self::with_enabled(cfg!(debug_assertions))
+    }
+
+    /// Create an accumulator with collection explicitly on or off
+    pub fn with_enabled(enabled: bool) -> Self {
+        Self { enabled: AtomicBool::new(enabled), samples: Mutex::new(HashMap::new()) }
+    }
+
+    /// Turn collection on or off; existing samples are kept
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Whether new durations are being recorded
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Record one render of a page; ignored while collection is disabled
+    pub fn record(&self, page_index: u16, duration: Duration) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut samples = self.lock();
+        let page_samples = samples.entry(page_index).or_default();
+        if page_samples.len() == MAX_SAMPLES_PER_PAGE {
+            page_samples.pop_front();
+        }
+        page_samples.push_back(duration);
+    }
+
+    /// Retained durations for a page, oldest first
+    pub fn page_samples(&self, page_index: u16) -> Vec<Duration> {
+        self.lock()
+            .get(&page_index)
+            .map(|samples| samples.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// Total number of retained samples across all pages
+    pub fn sample_count(&self) -> usize {
+        self.lock().values().map(VecDeque::len).sum()
+    }
+
+    /// Median render duration across all pages
+    pub fn p50(&self) -> Option<Duration> {
+        self.percentile(50)
+    }
+
+    /// 95th percentile render duration across all pages
+    pub fn p95(&self) -> Option<Duration> {
+        self.percentile(95)
+    }
+
+    /// Slowest recorded render
+    pub fn max(&self) -> Option<Duration> {
+        self.lock().values().flatten().max().copied()
+    }
+
+    /// Page with the slowest recorded render
+    pub fn slowest_page(&self) -> Option<u16> {
+        self.lock()
+            .iter()
+            .filter_map(|(page_index, samples)| Some((*page_index, samples.iter().max()?)))
+            .max_by_key(|(_, duration)| **duration)
+            .map(|(page_index, _)| page_index)
+    }
+
+    /// Discard all recorded samples
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Nearest-rank percentile over all samples
+    fn percentile(&self, percent: usize) -> Option<Duration> {
+        let mut all: Vec<Duration> = self.lock().values().flatten().copied().collect();
+        if all.is_empty() {
+            return None;
+        }
+
+        all.sort_unstable();
+        let rank = (percent * all.len()).div_ceil(100).max(1);
+        all.get(rank - 1).copied()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<u16, VecDeque<Duration>>> {
+        self.samples.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for RenderTimings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn test_records_are_aggregated() {
+        let timings = RenderTimings::with_enabled(true);
+        for (page_index, duration) in (1..=20).map(|i| ((i % 4) as u16, ms(i))) {
+            timings.record(page_index, duration);
+        }
+
+        assert_eq!(timings.sample_count(), 20);
+        assert_eq!(timings.page_samples(0), vec![ms(4), ms(8), ms(12), ms(16), ms(20)]);
+        assert_eq!(timings.p50(), Some(ms(10)));
+        assert_eq!(timings.p95(), Some(ms(19)));
+        assert_eq!(timings.max(), Some(ms(20)));
+        assert_eq!(timings.slowest_page(), Some(0));
+    }
+
+    #[test]
+    fn test_disabled_collection_records_nothing() {
+        let timings = RenderTimings::with_enabled(false);
+        timings.record(0, ms(5));
+
+        assert_eq!(timings.sample_count(), 0);
+        assert_eq!(timings.p50(), None);
+        assert_eq!(timings.max(), None);
+
+        timings.set_enabled(true);
+        timings.record(0, ms(5));
+        timings.set_enabled(false);
+        timings.record(0, ms(50));
+
+        assert_eq!(timings.page_samples(0), vec![ms(5)]);
+    }
+
+    #[test]
+    fn test_samples_per_page_are_capped() {
+        let timings = RenderTimings::with_enabled(true);
+        let total = MAX_SAMPLES_PER_PAGE as u64 + 10;
+        for i in 1..=total {
+            timings.record(0, ms(i));
+        }
+        timings.record(1, ms(1));
+
+        let page_samples = timings.page_samples(0);
+        assert_eq!(page_samples.len(), MAX_SAMPLES_PER_PAGE);
+        assert_eq!(page_samples.first(), Some(&ms(11)));
+        assert_eq!(page_samples.last(), Some(&ms(total)));
+        assert_eq!(timings.sample_count(), MAX_SAMPLES_PER_PAGE + 1);
+        assert_eq!(timings.max(), Some(ms(total)));
+    }
+
+    #[test]
+    fn test_clear() {
+        let timings = RenderTimings::with_enabled(true);
+        timings.record(3, ms(7));
+        timings.clear();
+
+        assert_eq!(timings.sample_count(), 0);
+        assert_eq!(timings.slowest_page(), None);
+    }
+}
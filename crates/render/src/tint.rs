@@ -0,0 +1,172 @@
+//! Post-rasterization page tints for dark mode and reading comfort
+//!
+//! Tints operate on tightly packed RGBA buffers as produced by pdfium and
+//! leave the alpha channel untouched.
+
+/// Color adjustment applied to a rendered page
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PageTint {
+    /// Leave pixels unchanged
+    #[default]
+    None,
+
+    /// Invert every color channel, turning white pages black
+    Invert,
+
+    /// Warm brown tone over the page
+    Sepia,
+
+    /// Invert luminance only, mapping white paper to dark gray and black text
+    /// to light gray while keeping hues recognizable
+    DarkInvert,
+}
+
+/// Luminance white paper is mapped to by `PageTint::DarkInvert`
+const DARK_INVERT_BACKGROUND: f32 = 24.0;
+
+/// Luminance black text is mapped to by `PageTint::DarkInvert`
+const DARK_INVERT_FOREGROUND: f32 = 232.0;
+
+/// Apply a tint to every pixel of an RGBA buffer in place
+///
+/// # Arguments
+/// * `buf` - Tightly packed RGBA pixels; the length must be a multiple of 4
+/// * `tint` - Tint to apply
+pub fn apply_page_tint(buf: &mut [u8], tint: PageTint) {
+    debug_assert!(
+        buf.len().is_multiple_of(4),
+        "pixel buffer length {} is not a multiple of 4",
+        buf.len()
+    );
+
+    if tint == PageTint::None {
+        return;
+    }
+
+    for pixel in buf.chunks_exact_mut(4) {
+        tint_pixel(pixel, tint);
+    }
+}
+
+/// Apply a tint to an RGBA buffer, leaving the given regions untouched
+///
+/// Useful for keeping embedded photos in their original colors while the
+/// surrounding page is tinted.
+///
+/// # Arguments
+/// * `buf` - Tightly packed RGBA pixels; the length must be a multiple of 4
+/// * `width` - Width of the image in pixels
+/// * `tint` - Tint to apply
+/// * `preserve` - Regions to skip as (x, y, width, height) in pixels
+pub fn apply_page_tint_preserving(
+    buf: &mut [u8],
+    width: u32,
+    tint: PageTint,
+    preserve: &[(u32, u32, u32, u32)],
+) {
+    debug_assert!(
+        buf.len().is_multiple_of(4),
+        "pixel buffer length {} is not a multiple of 4",
+        buf.len()
+    );
+
+    if tint == PageTint::None || width == 0 {
+        return;
+    }
+
+    for (index, pixel) in buf.chunks_exact_mut(4).enumerate() {
+        let x = index as u32 % width;
+        let y = index as u32 / width;
+        let preserved = preserve
+            .iter()
+            .any(|&(rx, ry, rw, rh)| x >= rx && x - rx < rw && y >= ry && y - ry < rh);
+
+        if !preserved {
+            tint_pixel(pixel, tint);
+        }
+    }
+}
+
+fn tint_pixel(pixel: &mut [u8], tint: PageTint) {
+    let [r, g, b] = [pixel[0], pixel[1], pixel[2]].map(f32::from);
+
+    let (r, g, b) = match tint {
+        PageTint::None => return,
+        PageTint::Invert => (255.0 - r, 255.0 - g, 255.0 - b),
+        PageTint::Sepia => (
+            0.393 * r + 0.769 * g + 0.189 * b,
+            0.349 * r + 0.686 * g + 0.168 * b,
+            0.272 * r + 0.534 * g + 0.131 * b,
+        ),
+        PageTint::DarkInvert => {
+            let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+            let target = DARK_INVERT_FOREGROUND
+                - luma / 255.0 * (DARK_INVERT_FOREGROUND - DARK_INVERT_BACKGROUND);
+            let shift = target - luma;
+            (r + shift, g + shift, b + shift)
+        }
+    };
+
+    pixel[0] = r.round().clamp(0.0, 255.0) as u8;
+    pixel[1] = g.round().clamp(0.0, 255.0) as u8;
+    pixel[2] = b.round().clamp(0.0, 255.0) as u8;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_none_is_a_no_op() {
+        let original = vec![255, 255, 255, 255, 12, 34, 56, 78];
+        let mut pixels = original.clone();
+
+        apply_page_tint(&mut pixels, PageTint::None);
+        assert_eq!(pixels, original);
+    }
+
+    #[test]
+    fn test_invert_flips_white_to_black() {
+        let mut pixels = vec![255, 255, 255, 255, 10, 20, 30, 128];
+
+        apply_page_tint(&mut pixels, PageTint::Invert);
+        assert_eq!(pixels, vec![0, 0, 0, 255, 245, 235, 225, 128]);
+    }
+
+    #[test]
+    fn test_sepia_warms_gray() {
+        let mut pixels = vec![128, 128, 128, 255];
+
+        apply_page_tint(&mut pixels, PageTint::Sepia);
+        assert!(pixels[0] > pixels[1] && pixels[1] > pixels[2]);
+        assert_eq!(pixels[3], 255);
+    }
+
+    #[test]
+    fn test_dark_invert_maps_paper_and_ink_to_soft_extremes() {
+        let mut pixels = vec![255, 255, 255, 255, 0, 0, 0, 255];
+
+        apply_page_tint(&mut pixels, PageTint::DarkInvert);
+        assert_eq!(pixels, vec![24, 24, 24, 255, 232, 232, 232, 255]);
+    }
+
+    #[test]
+    fn test_dark_invert_keeps_hue_ordering() {
+        let mut pixels = vec![200, 60, 40, 255];
+
+        apply_page_tint(&mut pixels, PageTint::DarkInvert);
+        assert!(pixels[0] > pixels[1] && pixels[1] > pixels[2]);
+    }
+
+    #[test]
+    fn test_preserved_regions_are_not_tinted() {
+        // 2x2 image, preserve the top-right pixel
+        let mut pixels = vec![255; 16];
+
+        apply_page_tint_preserving(&mut pixels, 2, PageTint::Invert, &[(1, 0, 1, 1)]);
+        assert_eq!(&pixels[0..4], &[0, 0, 0, 255]);
+        assert_eq!(&pixels[4..8], &[255, 255, 255, 255]);
+        assert_eq!(&pixels[8..12], &[0, 0, 0, 255]);
+        assert_eq!(&pixels[12..16], &[0, 0, 0, 255]);
+    }
+}
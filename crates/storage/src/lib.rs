@@ -1,5 +1,5 @@
 use directories::ProjectDirs;
-use doc_model::Preferences;
+use doc_model::{Preferences, ReaderState};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -14,6 +14,8 @@ pub enum StorageError {
     Io(#[from] std::io::Error),
     #[error("serialization error: {0}")]
     Serde(#[from] serde_json::Error),
+    #[error("unsupported preferences version {found} (newest supported is {max})")]
+    UnsupportedVersion { found: u32, max: u32 },
 }
 
 #[derive(Debug, Clone)]
@@ -23,10 +25,23 @@ pub struct Storage {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PreferencesEnvelope {
+    #[serde(default)]
     version: u32,
     preferences: Preferences,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct DocumentViewEnvelope {
+    key: String,
+    state: ReaderState,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvelopeHeader {
+    #[serde(default)]
+    version: u32,
+}
+
 impl Storage {
     pub fn from_default_project() -> Result<Self, StorageError> {
         let dirs = ProjectDirs::from("dev", "ButterPaper", "ButterPaper")
@@ -44,13 +59,34 @@ impl Storage {
     }
 
     pub fn load_preferences(&self) -> Result<Preferences, StorageError> {
+        self.load_preferences_with(|preferences| self.save_preferences(preferences))
+    }
+
+    /// Loads preferences, passing older envelopes to `resave` so they get the current version.
+    /// The re-save is best-effort: an older file that cannot be rewritten still loads.
+    fn load_preferences_with(
+        &self,
+        resave: impl FnOnce(&Preferences) -> Result<(), StorageError>,
+    ) -> Result<Preferences, StorageError> {
         let path = self.preferences_path();
         if !path.exists() {
             return Ok(Preferences::default());
         }
 
         let bytes = fs::read(path)?;
+        let header: EnvelopeHeader = serde_json::from_slice(&bytes)?;
+        if header.version > PREFS_SCHEMA_VERSION {
+            return Err(StorageError::UnsupportedVersion {
+                found: header.version,
+                max: PREFS_SCHEMA_VERSION,
+            });
+        }
+
         let envelope: PreferencesEnvelope = serde_json::from_slice(&bytes)?;
+        if envelope.version < PREFS_SCHEMA_VERSION {
+            // Older envelopes share the current layout; re-saving stamps the current version.
+            let _ = resave(&envelope.preferences);
+        }
 
         Ok(envelope.preferences)
     }
@@ -66,22 +102,70 @@ impl Storage {
         Ok(())
     }
 
+    pub fn load_document_view(&self, doc_key: &str) -> Result<ReaderState, StorageError> {
+        let path = self.document_view_path(doc_key);
+        if !path.exists() {
+            return Ok(ReaderState::default());
+        }
+
+        let envelope: DocumentViewEnvelope = serde_json::from_slice(&fs::read(path)?)?;
+        if envelope.key != doc_key {
+            // A different document hashed to the same file name.
+            return Ok(ReaderState::default());
+        }
+
+        Ok(envelope.state)
+    }
+
+    pub fn save_document_view(
+        &self,
+        doc_key: &str,
+        state: &ReaderState,
+    ) -> Result<(), StorageError> {
+        fs::create_dir_all(self.views_dir())?;
+
+        let envelope = DocumentViewEnvelope { key: doc_key.to_owned(), state: *state };
+        let bytes = serde_json::to_vec_pretty(&envelope)?;
+        fs::write(self.document_view_path(doc_key), bytes)?;
+        Ok(())
+    }
+
     fn preferences_path(&self) -> PathBuf {
         self.root.join("preferences.json")
     }
+
+    fn views_dir(&self) -> PathBuf {
+        self.root.join("views")
+    }
+
+    fn document_view_path(&self, doc_key: &str) -> PathBuf {
+        self.views_dir().join(format!("{:016x}.json", stable_hash(doc_key.as_bytes())))
+    }
+}
+
+/// FNV-1a; unlike `DefaultHasher` its output is fixed across Rust releases.
+fn stable_hash(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use doc_model::{ViewMode, ZoomMode};
 
     #[test]
     fn preferences_round_trip() {
         let temp = tempfile::tempdir().expect("temp dir should be created");
         let store = Storage::with_root(temp.path());
 
-        let prefs =
-            Preferences { prefer_tabs: false, show_tab_bar: false, allow_window_merge: false };
+        let prefs = Preferences {
+            prefer_tabs: false,
+            show_tab_bar: false,
+            allow_window_merge: false,
+            max_tabs: Some(8),
+        };
 
         store.save_preferences(&prefs).expect("save should succeed");
         let loaded = store.load_preferences().expect("load should succeed");
@@ -97,4 +181,99 @@ mod tests {
         let loaded = store.load_preferences().expect("load should succeed");
         assert_eq!(loaded, Preferences::default());
     }
+
+    #[test]
+    fn load_rejects_future_version() {
+        let temp = tempfile::tempdir().expect("temp dir should be created");
+        let store = Storage::with_root(temp.path());
+
+        let future = serde_json::json!({
+            "version": PREFS_SCHEMA_VERSION + 1,
+            "preferences": Preferences::default(),
+        });
+        fs::write(store.preferences_path(), future.to_string()).expect("write should succeed");
+
+        let err = store.load_preferences().expect_err("future version should be rejected");
+        assert!(matches!(
+            err,
+            StorageError::UnsupportedVersion { found, max }
+                if found == PREFS_SCHEMA_VERSION + 1 && max == PREFS_SCHEMA_VERSION
+        ));
+    }
+
+    #[test]
+    fn load_migrates_older_version_and_resaves() {
+        let temp = tempfile::tempdir().expect("temp dir should be created");
+        let store = Storage::with_root(temp.path());
+
+        let prefs = Preferences {
+            prefer_tabs: false,
+            show_tab_bar: true,
+            allow_window_merge: false,
+            max_tabs: None,
+        };
+        let legacy = serde_json::json!({ "preferences": prefs });
+        fs::write(store.preferences_path(), legacy.to_string()).expect("write should succeed");
+
+        let loaded = store.load_preferences().expect("load should succeed");
+        assert_eq!(loaded, prefs);
+
+        let bytes = fs::read(store.preferences_path()).expect("read should succeed");
+        let envelope: PreferencesEnvelope =
+            serde_json::from_slice(&bytes).expect("re-saved envelope should parse");
+        assert_eq!(envelope.version, PREFS_SCHEMA_VERSION);
+        assert_eq!(envelope.preferences, prefs);
+    }
+
+    #[test]
+    fn load_older_version_succeeds_when_resave_fails() {
+        let temp = tempfile::tempdir().expect("temp dir should be created");
+        let store = Storage::with_root(temp.path());
+
+        let prefs = Preferences { max_tabs: Some(4), ..Preferences::default() };
+        let legacy = serde_json::json!({ "preferences": prefs }).to_string();
+        fs::write(store.preferences_path(), &legacy).expect("write should succeed");
+
+        let loaded = store
+            .load_preferences_with(|_| {
+                Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "read-only").into())
+            })
+            .expect("load should succeed");
+        assert_eq!(loaded, prefs);
+
+        let bytes = fs::read_to_string(store.preferences_path()).expect("read should succeed");
+        assert_eq!(bytes, legacy);
+    }
+
+    #[test]
+    fn document_view_round_trip() {
+        let temp = tempfile::tempdir().expect("temp dir should be created");
+        let store = Storage::with_root(temp.path());
+        let key = "/docs/report.pdf";
+
+        let state = ReaderState {
+            view_mode: ViewMode::SinglePage,
+            zoom_mode: ZoomMode::Percent,
+            zoom_percent: 175,
+            crop_box: None,
+        };
+
+        store.save_document_view(key, &state).expect("save should succeed");
+        assert!(temp.path().join("views").is_dir());
+
+        let loaded = store.load_document_view(key).expect("load should succeed");
+        assert_eq!(loaded, state);
+
+        let loaded = store.load_document_view("/docs/other.pdf").expect("load should succeed");
+        assert_eq!(loaded, ReaderState::default());
+    }
+
+    #[test]
+    fn document_view_defaults_when_absent() {
+        let temp = tempfile::tempdir().expect("temp dir should be created");
+        let store = Storage::with_root(temp.path());
+
+        let loaded = store.load_document_view("missing").expect("load should succeed");
+        assert_eq!(loaded, ReaderState::default());
+    }
 }
@@ -33,6 +33,35 @@ pub struct SelectedAsset {
     pub channel: UpdateChannel,
     pub asset_name: String,
     pub download_url: String,
+    pub size: Option<u64>,
+}
+
+impl SelectedAsset {
+    pub fn download_plan(&self) -> DownloadPlan {
+        DownloadPlan { url: self.download_url.clone(), expected_size: self.size, etag: None }
+    }
+}
+
+/// Enough state for the installer layer to start or continue a partial download.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadPlan {
+    pub url: String,
+    pub expected_size: Option<u64>,
+    pub etag: Option<String>,
+}
+
+impl DownloadPlan {
+    /// Returns the `Range` header for a partial GET, or `None` when the download should start
+    /// from scratch (nothing on disk yet, or the local file is already at/over the expected size).
+    pub fn resume_range(&self, bytes_already: u64) -> Option<(String, String)> {
+        if bytes_already == 0 {
+            return None;
+        }
+        if self.expected_size.is_some_and(|size| bytes_already >= size) {
+            return None;
+        }
+        Some(("Range".to_string(), format!("bytes={bytes_already}-")))
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -73,6 +102,8 @@ struct GhRelease {
 struct GhAsset {
     name: String,
     browser_download_url: String,
+    #[serde(default)]
+    size: Option<u64>,
 }
 
 pub fn detect_platform() -> Option<Platform> {
@@ -225,29 +256,75 @@ fn select_update_asset_from_releases(
         channel,
         asset_name: asset.name.clone(),
         download_url: asset.browser_download_url.clone(),
+        size: asset.size,
     }))
 }
 
-fn fetch_releases(repo: Repo) -> Result<Vec<GhRelease>, UpdateError> {
+/// How many pages of releases `check_for_update` follows before ranking.
+pub const DEFAULT_MAX_RELEASE_PAGES: usize = 5;
+
+/// Extracts the `rel="next"` target from a GitHub `Link` header.
+fn next_page_url(link_header: &str) -> Option<String> {
+    link_header.split(',').find_map(|part| {
+        let (target, params) = part.split_once(';')?;
+        let is_next = params.split(';').any(|param| param.trim() == "rel=\"next\"");
+        if !is_next {
+            return None;
+        }
+        let url = target.trim().strip_prefix('<')?.strip_suffix('>')?;
+        Some(url.to_string())
+    })
+}
+
+/// Collects releases from `first_url` and up to `max_pages - 1` following pages.
+/// `fetch_page` returns a page of releases plus the next page URL, if any.
+fn collect_release_pages<F>(
+    first_url: String,
+    max_pages: usize,
+    mut fetch_page: F,
+) -> Result<Vec<GhRelease>, UpdateError>
+where
+    F: FnMut(&str) -> Result<(Vec<GhRelease>, Option<String>), UpdateError>,
+{
+    let mut releases = Vec::new();
+    let mut next = Some(first_url);
+
+    for _ in 0..max_pages.max(1) {
+        let Some(url) = next.take() else {
+            break;
+        };
+        let (page, next_url) = fetch_page(&url)?;
+        releases.extend(page);
+        next = next_url;
+    }
+
+    Ok(releases)
+}
+
+fn fetch_releases(repo: Repo, max_pages: usize) -> Result<Vec<GhRelease>, UpdateError> {
     let url = format!(
         "https://api.github.com/repos/{}/{}/releases?per_page=20",
         repo.owner, repo.name
     );
 
     let agent = ureq::agent();
-    let resp = agent
-        .get(&url)
-        .set("Accept", "application/vnd.github+json")
-        .set("User-Agent", "ButterPaper-Updater")
-        .call()
-        .map_err(|e| UpdateError::Network(e.to_string()))?;
-
-    let body = resp
-        .into_string()
-        .map_err(|e| UpdateError::InvalidResponse(e.to_string()))?;
-
-    serde_json::from_str::<Vec<GhRelease>>(&body)
-        .map_err(|e| UpdateError::InvalidResponse(e.to_string()))
+    collect_release_pages(url, max_pages, |url| {
+        let resp = agent
+            .get(url)
+            .set("Accept", "application/vnd.github+json")
+            .set("User-Agent", "ButterPaper-Updater")
+            .call()
+            .map_err(|e| UpdateError::Network(e.to_string()))?;
+
+        let next_url = resp.header("Link").and_then(next_page_url);
+        let body = resp
+            .into_string()
+            .map_err(|e| UpdateError::InvalidResponse(e.to_string()))?;
+
+        let page = serde_json::from_str::<Vec<GhRelease>>(&body)
+            .map_err(|e| UpdateError::InvalidResponse(e.to_string()))?;
+        Ok((page, next_url))
+    })
 }
 
 pub fn check_for_update(
@@ -257,7 +334,26 @@ pub fn check_for_update(
     arch: Arch,
     current_version: &Version,
 ) -> Result<Option<SelectedAsset>, UpdateError> {
-    let releases = fetch_releases(repo)?;
+    check_for_update_with_max_pages(
+        repo,
+        channel,
+        platform,
+        arch,
+        current_version,
+        DEFAULT_MAX_RELEASE_PAGES,
+    )
+}
+
+/// Like `check_for_update`, but follows at most `max_pages` pages of releases.
+pub fn check_for_update_with_max_pages(
+    repo: Repo,
+    channel: UpdateChannel,
+    platform: Platform,
+    arch: Arch,
+    current_version: &Version,
+    max_pages: usize,
+) -> Result<Option<SelectedAsset>, UpdateError> {
+    let releases = fetch_releases(repo, max_pages)?;
     select_update_asset_from_releases(repo, channel, platform, arch, current_version, &releases)
 }
 
@@ -275,6 +371,7 @@ mod tests {
                 .map(|name| GhAsset {
                     name: name.to_string(),
                     browser_download_url: format!("https://example.invalid/{name}"),
+                    size: Some(1024),
                 })
                 .collect(),
         }
@@ -364,4 +461,126 @@ mod tests {
 
         assert_eq!(sel.tag_name, "v0.0.2");
     }
+
+    #[test]
+    fn next_page_url_follows_rel_next() {
+        let header = "<https://api.github.com/releases?page=2>; rel=\"next\", \
+                      <https://api.github.com/releases?page=4>; rel=\"last\"";
+        assert_eq!(
+            next_page_url(header).as_deref(),
+            Some("https://api.github.com/releases?page=2")
+        );
+
+        let last_page = "<https://api.github.com/releases?page=1>; rel=\"prev\"";
+        assert_eq!(next_page_url(last_page), None);
+    }
+
+    #[test]
+    fn ranking_spans_all_fetched_pages() {
+        let repo = Repo::new("o", "r");
+        let current = Version::parse("0.0.1").unwrap();
+        let v002 = Version::parse("0.0.2").unwrap();
+        let stable_asset_002 =
+            expected_asset_name(UpdateChannel::Stable, Platform::Linux, Arch::X64, &v002).unwrap();
+
+        let mut requested = Vec::new();
+        let releases =
+            collect_release_pages("page-1".to_string(), DEFAULT_MAX_RELEASE_PAGES, |url| {
+                requested.push(url.to_string());
+                match url {
+                    "page-1" => Ok((
+                        vec![
+                            mk_release("v0.0.3-beta.2", true, &[]),
+                            mk_release("v0.0.3-beta.1", true, &[]),
+                            mk_release("v0.0.1", false, &[]),
+                        ],
+                        Some("page-2".to_string()),
+                    )),
+                    _ => Ok((vec![mk_release("v0.0.2", false, &[&stable_asset_002])], None)),
+                }
+            })
+            .unwrap();
+
+        assert_eq!(requested, vec!["page-1", "page-2"]);
+
+        let sel = select_update_asset_from_releases(
+            repo,
+            UpdateChannel::Stable,
+            Platform::Linux,
+            Arch::X64,
+            &current,
+            &releases,
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(sel.tag_name, "v0.0.2");
+    }
+
+    #[test]
+    fn release_pagination_stops_at_max_pages() {
+        let mut requested = 0;
+        let releases = collect_release_pages("page".to_string(), 2, |_| {
+            requested += 1;
+            Ok((vec![mk_release("v0.0.1", false, &[])], Some("page".to_string())))
+        })
+        .unwrap();
+
+        assert_eq!(requested, 2);
+        assert_eq!(releases.len(), 2);
+    }
+
+    fn mk_plan(expected_size: Option<u64>) -> DownloadPlan {
+        DownloadPlan {
+            url: "https://example.invalid/ButterPaper-v0.0.2-macos-x64.zip".to_string(),
+            expected_size,
+            etag: None,
+        }
+    }
+
+    #[test]
+    fn download_plan_carries_asset_url_and_size() {
+        let repo = Repo::new("o", "r");
+        let current = Version::parse("0.0.1").unwrap();
+        let v002 = Version::parse("0.0.2").unwrap();
+        let asset =
+            expected_asset_name(UpdateChannel::Stable, Platform::Macos, Arch::X64, &v002).unwrap();
+        let releases = vec![mk_release("v0.0.2", false, &[&asset])];
+
+        let sel = select_update_asset_from_releases(
+            repo,
+            UpdateChannel::Stable,
+            Platform::Macos,
+            Arch::X64,
+            &current,
+            &releases,
+        )
+        .unwrap()
+        .unwrap();
+
+        let plan = sel.download_plan();
+        assert_eq!(plan.url, sel.download_url);
+        assert_eq!(plan.expected_size, Some(1024));
+        assert_eq!(plan.etag, None);
+    }
+
+    #[test]
+    fn resume_range_is_none_for_fresh_download() {
+        assert_eq!(mk_plan(Some(1024)).resume_range(0), None);
+        assert_eq!(mk_plan(None).resume_range(0), None);
+    }
+
+    #[test]
+    fn resume_range_requests_remaining_bytes() {
+        let range = mk_plan(Some(1024)).resume_range(512);
+        assert_eq!(range, Some(("Range".to_string(), "bytes=512-".to_string())));
+
+        let range = mk_plan(None).resume_range(300);
+        assert_eq!(range, Some(("Range".to_string(), "bytes=300-".to_string())));
+    }
+
+    #[test]
+    fn resume_range_is_none_when_already_complete() {
+        assert_eq!(mk_plan(Some(1024)).resume_range(1024), None);
+    }
 }
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, VecDeque};
 use std::hash::Hash;
 use std::ops::RangeInclusive;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ViewMode {
@@ -15,6 +17,14 @@ pub enum ZoomMode {
     FitWidth,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MemoryPressure {
+    Normal,
+    Warm,
+    Hot,
+    Critical,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ViewportState {
     pub mode: ViewMode,
@@ -28,15 +38,36 @@ pub struct ViewportState {
     pub page_spacing_px: f32,
 }
 
+/// Page size (width, height) in px at 100% zoom that a new `ViewportState` fits
+/// against before a document is loaded: US Letter.
+pub const DEFAULT_FIT_PAGE_SIZE_PX: (f32, f32) = (612.0, 792.0);
+
+/// Display metrics a `ViewportState` starts from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewportConfig {
+    pub width_px: f32,
+    pub height_px: f32,
+    pub dpr: f32,
+}
+
+impl Default for ViewportConfig {
+    fn default() -> Self {
+        Self { width_px: 1280.0, height_px: 800.0, dpr: 1.0 }
+    }
+}
+
+/// The default state keeps 100% zoom; only `with_viewport` / `with_config`
+/// compute a fit zoom for their display metrics.
 impl Default for ViewportState {
     fn default() -> Self {
+        let config = ViewportConfig::default();
         Self {
             mode: ViewMode::Continuous,
             zoom_mode: ZoomMode::FitPage,
             zoom_percent: 100,
-            viewport_width_px: 1280.0,
-            viewport_height_px: 800.0,
-            dpr: 1.0,
+            viewport_width_px: config.width_px,
+            viewport_height_px: config.height_px,
+            dpr: config.dpr,
             scroll_offset_px: 0.0,
             page_heights_px: vec![1000.0],
             page_spacing_px: 16.0,
@@ -44,13 +75,87 @@ impl Default for ViewportState {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+impl ViewportState {
+    pub fn with_viewport(width_px: f32, height_px: f32, dpr: f32) -> Self {
+        Self::with_config(ViewportConfig { width_px, height_px, dpr })
+    }
+
+    /// Starts from `config`'s display metrics, with the fit zoom computed for them.
+    pub fn with_config(config: ViewportConfig) -> Self {
+        let mut state = Self { dpr: config.dpr, ..Self::default() };
+        state.on_viewport_resized(config.width_px, config.height_px, DEFAULT_FIT_PAGE_SIZE_PX);
+        state
+    }
+
+    /// Applies a new viewport size. In a fit mode the zoom is recomputed against
+    /// `page_size_px`, the current page's (width, height) in px at 100% zoom, and
+    /// the page heights rescaled to match; the point at the viewport center stays
+    /// at the same fraction of the document.
+    pub fn on_viewport_resized(&mut self, width_px: f32, height_px: f32, page_size_px: (f32, f32)) {
+        let center_fraction = {
+            let total = self.total_height_px();
+            if total > 0.0 {
+                (self.scroll_offset_px + self.viewport_height_px / 2.0) / total
+            } else {
+                0.0
+            }
+        };
+
+        self.viewport_width_px = width_px;
+        self.viewport_height_px = height_px;
+
+        let (page_width, page_height) = page_size_px;
+        let zoom_percent = match self.zoom_mode {
+            ZoomMode::Percent => return,
+            ZoomMode::FitWidth => fit_width_percent(width_px, page_width, self.dpr),
+            ZoomMode::FitPage => {
+                fit_page_percent(width_px, height_px, page_width, page_height, self.dpr)
+            }
+        };
+
+        if self.zoom_percent > 0 && zoom_percent != self.zoom_percent {
+            let ratio = zoom_percent as f32 / self.zoom_percent as f32;
+            for page_height in &mut self.page_heights_px {
+                *page_height *= ratio;
+            }
+        }
+        self.zoom_percent = zoom_percent;
+
+        let total = self.total_height_px();
+        let offset = center_fraction * total - height_px / 2.0;
+        self.scroll_offset_px = clamp_scroll_offset(offset, total, height_px, 1.0);
+    }
+
+    /// Lays pages out from their heights in px at 100% zoom, scaled by the current
+    /// zoom and dpr. When a view crop is set, `crop_height_px` (also at 100% zoom)
+    /// replaces each page's height, capped at the page's own height.
+    pub fn set_page_heights(&mut self, base_heights_px: &[f32], crop_height_px: Option<f32>) {
+        let scale = self.zoom_percent as f32 / 100.0 * self.dpr;
+        self.page_heights_px = base_heights_px
+            .iter()
+            .map(|&height| {
+                let height = match crop_height_px {
+                    Some(crop) => crop.max(0.0).min(height),
+                    None => height,
+                };
+                height * scale
+            })
+            .collect();
+    }
+
+    fn total_height_px(&self) -> f32 {
+        let gaps = self.page_heights_px.len().saturating_sub(1) as f32 * self.page_spacing_px;
+        self.page_heights_px.iter().sum::<f32>() + gaps
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RenderKind {
     Page,
     Thumbnail,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum RenderPriority {
     Visible,
     Thumbnail,
@@ -67,7 +172,7 @@ impl RenderPriority {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RenderJobKey {
     pub document_id: u64,
     pub page_index: u32,
@@ -75,18 +180,30 @@ pub struct RenderJobKey {
     pub kind: RenderKind,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RenderJob {
     pub key: RenderJobKey,
     pub priority: RenderPriority,
     pub generation: u64,
 }
 
+/// Number of finished jobs `RenderQueue::recent_completions` keeps.
+pub const RECENT_COMPLETIONS_CAPACITY: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenderCompletion {
+    pub key: RenderJobKey,
+    /// Queue generation current when the job finished.
+    pub generation: u64,
+    pub duration: Duration,
+}
+
 #[derive(Debug, Default)]
 pub struct RenderQueue {
     generation: u64,
     pending: HashMap<RenderJobKey, (RenderPriority, u64)>,
     order: VecDeque<RenderJobKey>,
+    completions: VecDeque<RenderCompletion>,
 }
 
 impl RenderQueue {
@@ -148,6 +265,35 @@ impl RenderQueue {
         Some(RenderJob { key, priority, generation })
     }
 
+    /// Pending jobs in the order `pop_next` would return them, without consuming them.
+    pub fn snapshot(&self) -> Vec<(RenderJobKey, RenderPriority, u64)> {
+        let mut jobs: Vec<(RenderJobKey, RenderPriority, u64)> = self
+            .order
+            .iter()
+            .filter_map(|key| {
+                self.pending.get(key).map(|(priority, generation)| (*key, *priority, *generation))
+            })
+            .collect();
+
+        jobs.sort_by_key(|(_, priority, _)| priority.rank());
+        jobs
+    }
+
+    /// Records a finished job for telemetry, keeping the newest
+    /// `RECENT_COMPLETIONS_CAPACITY` entries.
+    pub fn complete(&mut self, key: RenderJobKey, duration: Duration) {
+        if self.completions.len() == RECENT_COMPLETIONS_CAPACITY {
+            self.completions.pop_front();
+        }
+
+        self.completions.push_back(RenderCompletion { key, generation: self.generation, duration });
+    }
+
+    /// Finished jobs, oldest first.
+    pub fn recent_completions(&self) -> impl Iterator<Item = &RenderCompletion> {
+        self.completions.iter()
+    }
+
     pub fn len(&self) -> usize {
         self.pending.len()
     }
@@ -226,6 +372,212 @@ where
     }
 }
 
+/// Inertial scrolling after a fling. Velocity decays exponentially, so the
+/// distance covered over a span of time is the same whatever the frame rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KineticScroller {
+    /// Current velocity in px per second.
+    pub velocity: f32,
+    /// Exponential decay rate per second; higher stops sooner. Always finite
+    /// and positive, since `tick` divides by it.
+    friction: f32,
+}
+
+impl KineticScroller {
+    pub const DEFAULT_FRICTION: f32 = 4.0;
+    /// Below this speed (px per second) scrolling snaps to a stop.
+    pub const STOP_VELOCITY: f32 = 5.0;
+
+    /// Non-finite or non-positive `friction` falls back to `DEFAULT_FRICTION`.
+    pub fn new(friction: f32) -> Self {
+        let mut scroller = Self { velocity: 0.0, friction: Self::DEFAULT_FRICTION };
+        scroller.set_friction(friction);
+        scroller
+    }
+
+    pub fn friction(&self) -> f32 {
+        self.friction
+    }
+
+    /// Non-finite or non-positive `friction` falls back to `DEFAULT_FRICTION`.
+    pub fn set_friction(&mut self, friction: f32) {
+        self.friction =
+            if friction.is_finite() && friction > 0.0 { friction } else { Self::DEFAULT_FRICTION };
+    }
+
+    pub fn on_fling(&mut self, initial_velocity: f32) {
+        self.velocity = if initial_velocity.is_finite() { initial_velocity } else { 0.0 };
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.velocity != 0.0
+    }
+
+    /// Advances by `dt` seconds and returns the scroll delta to apply this frame.
+    pub fn tick(&mut self, dt: f32) -> f32 {
+        if !self.is_active() || !dt.is_finite() || dt <= 0.0 {
+            return 0.0;
+        }
+
+        let decay = (-self.friction * dt).exp();
+        let delta = self.velocity * (1.0 - decay) / self.friction;
+
+        self.velocity *= decay;
+        if self.velocity.abs() < Self::STOP_VELOCITY {
+            self.velocity = 0.0;
+        }
+
+        delta
+    }
+}
+
+impl Default for KineticScroller {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_FRICTION)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Cubic ease-in-out: slow start, fast middle, slow finish.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Maps animation progress in `0..=1` to eased progress in `0..=1`.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+        }
+    }
+}
+
+/// Animated scroll from one offset to another, sampled by elapsed time so the
+/// caller can drive it from whatever frame clock it has.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollAnimator {
+    pub from: f32,
+    pub to: f32,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl ScrollAnimator {
+    pub fn new(from: f32, to: f32, duration: Duration, easing: Easing) -> Self {
+        Self { from, to, duration, easing }
+    }
+
+    /// Scroll offset `elapsed` after the animation started.
+    pub fn position(&self, elapsed: Duration) -> f32 {
+        if self.is_finished(elapsed) {
+            return self.to;
+        }
+
+        let t = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        self.from + (self.to - self.from) * self.easing.apply(t)
+    }
+
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        elapsed >= self.duration
+    }
+}
+
+/// Page y offsets for continuous layout, built one page at a time as
+/// dimensions arrive so the first page can be placed before the rest are known.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PageYOffsets {
+    offsets: Vec<f32>,
+    total_height_px: f32,
+    page_spacing_px: f32,
+}
+
+impl PageYOffsets {
+    pub fn new(page_spacing_px: f32) -> Self {
+        Self {
+            offsets: Vec::new(),
+            total_height_px: 0.0,
+            page_spacing_px: page_spacing_px.max(0.0),
+        }
+    }
+
+    /// Appends the next page and returns its y offset.
+    pub fn push(&mut self, page_height_px: f32) -> f32 {
+        let offset =
+            if self.offsets.is_empty() { 0.0 } else { self.total_height_px + self.page_spacing_px };
+
+        self.offsets.push(offset);
+        self.total_height_px = offset + page_height_px.max(0.0);
+        offset
+    }
+
+    pub fn offsets(&self) -> &[f32] {
+        &self.offsets
+    }
+
+    pub fn total_height_px(&self) -> f32 {
+        self.total_height_px
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+/// Thumbnails to lay out for a uniform-height strip, widened by `overscan` rows
+/// on each side. Returns an empty range for an empty document.
+pub fn visible_thumbnail_range(
+    scroll_offset: f32,
+    viewport_height: f32,
+    thumb_height: f32,
+    spacing: f32,
+    page_count: u32,
+    overscan: u32,
+) -> RangeInclusive<u32> {
+    if page_count == 0 {
+        // Start past the end so the range yields nothing.
+        return RangeInclusive::new(1, 0);
+    }
+
+    let last = page_count - 1;
+    let stride = thumb_height.max(0.0) + spacing.max(0.0);
+    if !stride.is_finite() || stride <= 0.0 {
+        return 0..=last;
+    }
+
+    let top = if scroll_offset.is_finite() { scroll_offset.max(0.0) } else { 0.0 };
+    let bottom = top + viewport_height.max(0.0);
+
+    let first_visible = ((top / stride).floor() as u32).min(last);
+    // A thumbnail starting exactly at the bottom edge is not visible yet.
+    let last_visible = ((bottom / stride).ceil() - 1.0).max(0.0) as u32;
+    let last_visible = last_visible.clamp(first_visible, last);
+
+    first_visible.saturating_sub(overscan)..=last_visible.saturating_add(overscan).min(last)
+}
+
+/// Prefetch radius to use under `pressure`: the full radius when memory is
+/// plentiful, half when warm, and no prefetching at all once it runs hot.
+pub fn effective_prefetch_radius(base_radius: u32, pressure: MemoryPressure) -> u32 {
+    match pressure {
+        MemoryPressure::Normal => base_radius,
+        MemoryPressure::Warm => base_radius / 2,
+        MemoryPressure::Hot | MemoryPressure::Critical => 0,
+    }
+}
+
 pub fn prefetch_page_indices(current_page_index: u32, page_count: u32, radius: u32) -> Vec<u32> {
     if page_count == 0 {
         return Vec::new();
@@ -248,12 +600,36 @@ pub fn prefetch_page_indices(current_page_index: u32, page_count: u32, radius: u
     pages
 }
 
+/// Zoom percents the fit helpers clamp to unless a caller supplies its own range.
+pub const DEFAULT_ZOOM_RANGE_PERCENT: RangeInclusive<u16> = 10..=1600;
+
 pub fn fit_width_percent(viewport_width_px: f32, page_width_px: f32, dpr: f32) -> u16 {
-    if viewport_width_px <= 0.0 || page_width_px <= 0.0 || dpr <= 0.0 {
-        return 100;
+    fit_width_percent_clamped(viewport_width_px, page_width_px, dpr, DEFAULT_ZOOM_RANGE_PERCENT)
+}
+
+pub fn fit_width_percent_clamped(
+    viewport_width_px: f32,
+    page_width_px: f32,
+    dpr: f32,
+    range: RangeInclusive<u16>,
+) -> u16 {
+    fit_width_percent_padded(viewport_width_px, page_width_px, dpr, 0.0, range)
+}
+
+/// Fit-width zoom that leaves `padding_px` of screen space on each side of the page.
+pub fn fit_width_percent_padded(
+    viewport_width_px: f32,
+    page_width_px: f32,
+    dpr: f32,
+    padding_px: f32,
+    range: RangeInclusive<u16>,
+) -> u16 {
+    let usable_width = viewport_width_px - padding_px.max(0.0) * 2.0;
+    if usable_width <= 0.0 || page_width_px <= 0.0 || dpr <= 0.0 {
+        return clamp_percent(100.0, &range);
     }
 
-    ((viewport_width_px / (page_width_px * dpr)) * 100.0).round().clamp(10.0, 1600.0) as u16
+    clamp_percent((usable_width / (page_width_px * dpr)) * 100.0, &range)
 }
 
 pub fn fit_page_percent(
@@ -263,19 +639,145 @@ pub fn fit_page_percent(
     page_height_px: f32,
     dpr: f32,
 ) -> u16 {
-    if viewport_width_px <= 0.0
-        || viewport_height_px <= 0.0
+    fit_page_percent_clamped(
+        viewport_width_px,
+        viewport_height_px,
+        page_width_px,
+        page_height_px,
+        dpr,
+        DEFAULT_ZOOM_RANGE_PERCENT,
+    )
+}
+
+pub fn fit_page_percent_clamped(
+    viewport_width_px: f32,
+    viewport_height_px: f32,
+    page_width_px: f32,
+    page_height_px: f32,
+    dpr: f32,
+    range: RangeInclusive<u16>,
+) -> u16 {
+    fit_page_percent_padded(
+        viewport_width_px,
+        viewport_height_px,
+        page_width_px,
+        page_height_px,
+        dpr,
+        0.0,
+        range,
+    )
+}
+
+/// Fit-page zoom that leaves `padding_px` of screen space around every edge of the page.
+pub fn fit_page_percent_padded(
+    viewport_width_px: f32,
+    viewport_height_px: f32,
+    page_width_px: f32,
+    page_height_px: f32,
+    dpr: f32,
+    padding_px: f32,
+    range: RangeInclusive<u16>,
+) -> u16 {
+    let inset = padding_px.max(0.0) * 2.0;
+    let usable_width = viewport_width_px - inset;
+    let usable_height = viewport_height_px - inset;
+    if usable_width <= 0.0
+        || usable_height <= 0.0
         || page_width_px <= 0.0
         || page_height_px <= 0.0
         || dpr <= 0.0
     {
-        return 100;
+        return clamp_percent(100.0, &range);
+    }
+
+    let width = usable_width / (page_width_px * dpr);
+    let height = usable_height / (page_height_px * dpr);
+
+    clamp_percent(width.min(height) * 100.0, &range)
+}
+
+/// Zoom that fits a two-page spread, side by side, inside the viewport.
+///
+/// Pages are `(width_px, height_px)` at 100% zoom. Either side may be absent,
+/// as with a cover page shown on its own; the gap only applies when both are
+/// present. `spacing_px` is a screen-space gap and does not scale with zoom.
+pub fn fit_two_pages_percent(
+    viewport_width_px: f32,
+    viewport_height_px: f32,
+    left_page: Option<(f32, f32)>,
+    right_page: Option<(f32, f32)>,
+    spacing_px: f32,
+    dpr: f32,
+) -> u16 {
+    let mut spread_width = 0.0_f32;
+    let mut spread_height = 0.0_f32;
+    let mut count = 0;
+
+    for (page_width, page_height) in [left_page, right_page].into_iter().flatten() {
+        if page_width <= 0.0 || page_height <= 0.0 {
+            continue;
+        }
+
+        spread_width += page_width;
+        spread_height = spread_height.max(page_height);
+        count += 1;
+    }
+
+    if count == 0 || viewport_height_px <= 0.0 || dpr <= 0.0 {
+        return clamp_percent(100.0, &DEFAULT_ZOOM_RANGE_PERCENT);
+    }
+
+    let gap = if count == 2 { spacing_px.max(0.0) } else { 0.0 };
+    let usable_width = viewport_width_px - gap;
+    if usable_width <= 0.0 {
+        return clamp_percent(100.0, &DEFAULT_ZOOM_RANGE_PERCENT);
     }
 
-    let width = viewport_width_px / (page_width_px * dpr);
-    let height = viewport_height_px / (page_height_px * dpr);
+    let width = usable_width / (spread_width * dpr);
+    let height = viewport_height_px / (spread_height * dpr);
 
-    (width.min(height) * 100.0).round().clamp(10.0, 1600.0) as u16
+    clamp_percent(width.min(height) * 100.0, &DEFAULT_ZOOM_RANGE_PERCENT)
+}
+
+/// Clamps a continuous-scroll offset so the bottom of the last page lines up
+/// with the bottom of the viewport rather than scrolling into empty space.
+///
+/// `total_height` is the unscaled document height; `zoom_scale` is 1.0 at 100%.
+pub fn clamp_scroll_offset(
+    offset: f32,
+    total_height: f32,
+    viewport_height: f32,
+    zoom_scale: f32,
+) -> f32 {
+    clamp_scroll_offset_with_min_visible(
+        offset,
+        total_height,
+        viewport_height,
+        zoom_scale,
+        viewport_height,
+    )
+}
+
+/// Like [`clamp_scroll_offset`], but only keeps `min_visible_px` of the end of
+/// the document on screen. Values above the viewport height are treated as the
+/// viewport height.
+pub fn clamp_scroll_offset_with_min_visible(
+    offset: f32,
+    total_height: f32,
+    viewport_height: f32,
+    zoom_scale: f32,
+    min_visible_px: f32,
+) -> f32 {
+    if !offset.is_finite() {
+        return 0.0;
+    }
+
+    let zoom_scale = if zoom_scale.is_finite() && zoom_scale > 0.0 { zoom_scale } else { 1.0 };
+    let content_height = (total_height * zoom_scale).max(0.0);
+    let min_visible = min_visible_px.clamp(0.0, viewport_height.max(0.0));
+    let max_offset = (content_height - min_visible).max(0.0);
+
+    offset.clamp(0.0, max_offset)
 }
 
 pub fn visible_pages(state: &ViewportState) -> RangeInclusive<u32> {
@@ -314,6 +816,36 @@ pub fn switch_mode_preserving_page(
     state.scroll_offset_px = page_start_offset(target_page, state);
 }
 
+/// In single-page mode, scrolls to the top of the page under the scroll
+/// offset and returns where to draw it in the viewport: centered on each axis
+/// where it fits, top/left-aligned where it is larger. Page sizes are on-screen
+/// px. Returns `None` in continuous mode, leaving the state untouched.
+pub fn recenter_on_page(
+    state: &mut ViewportState,
+    page_width_px: f32,
+    page_height_px: f32,
+) -> Option<(f32, f32)> {
+    if state.mode != ViewMode::SinglePage {
+        return None;
+    }
+
+    if !state.page_heights_px.is_empty() {
+        let page = page_at_offset(state.scroll_offset_px.max(0.0), state);
+        state.scroll_offset_px = page_start_offset(page, state);
+    }
+
+    let x = ((state.viewport_width_px - page_width_px) / 2.0).max(0.0);
+    let y = ((state.viewport_height_px - page_height_px) / 2.0).max(0.0);
+    Some((x, y))
+}
+
+fn clamp_percent(percent: f32, range: &RangeInclusive<u16>) -> u16 {
+    let min = *range.start();
+    let max = (*range.end()).max(min);
+
+    percent.round().clamp(min as f32, max as f32) as u16
+}
+
 fn page_at_offset(offset: f32, state: &ViewportState) -> u32 {
     let mut cursor = 0.0;
 
@@ -361,6 +893,91 @@ mod tests {
         assert_eq!(percent, 40);
     }
 
+    #[test]
+    fn fit_helpers_clamp_to_custom_range() {
+        // 1500 / 500 = 300%, above the 200% ceiling.
+        assert_eq!(fit_width_percent_clamped(1500.0, 500.0, 1.0, 25..=200), 200);
+        assert_eq!(fit_page_percent_clamped(1500.0, 1500.0, 500.0, 500.0, 1.0, 25..=200), 200);
+
+        // 100 / 1000 = 10%, below the 25% floor.
+        assert_eq!(fit_width_percent_clamped(100.0, 1000.0, 1.0, 25..=200), 25);
+
+        // In-range results pass through untouched.
+        assert_eq!(fit_width_percent_clamped(1000.0, 500.0, 1.0, 25..=200), 200);
+        assert_eq!(fit_width_percent_clamped(750.0, 500.0, 1.0, 25..=200), 150);
+    }
+
+    #[test]
+    fn fit_helpers_default_range_is_unchanged() {
+        for (viewport, page) in [(1000.0, 500.0), (100_000.0, 100.0), (10.0, 1000.0)] {
+            assert_eq!(
+                fit_width_percent(viewport, page, 1.0),
+                fit_width_percent_clamped(viewport, page, 1.0, DEFAULT_ZOOM_RANGE_PERCENT)
+            );
+        }
+
+        assert_eq!(fit_width_percent(10.0, 1000.0, 1.0), 10);
+        assert_eq!(fit_page_percent(1000.0, 800.0, 500.0, 2000.0, 1.0), 40);
+        assert_eq!(fit_page_percent(0.0, 800.0, 500.0, 2000.0, 1.0), 100);
+    }
+
+    #[test]
+    fn fit_padding_shrinks_the_fit() {
+        let range = DEFAULT_ZOOM_RANGE_PERCENT;
+
+        // (1000 - 40) / 500 = 192% versus 200% unpadded.
+        assert_eq!(fit_width_percent_padded(1000.0, 500.0, 1.0, 20.0, range.clone()), 192);
+        assert_eq!(fit_width_percent_padded(1000.0, 500.0, 1.0, 0.0, range.clone()), 200);
+
+        // Height binds: (800 - 40) / 2000 = 38% versus 40% unpadded.
+        assert_eq!(
+            fit_page_percent_padded(1000.0, 800.0, 500.0, 2000.0, 1.0, 20.0, range.clone()),
+            38
+        );
+        assert_eq!(
+            fit_page_percent_padded(1000.0, 800.0, 500.0, 2000.0, 1.0, 0.0, range.clone()),
+            fit_page_percent(1000.0, 800.0, 500.0, 2000.0, 1.0)
+        );
+
+        // Padding that swallows the viewport falls back like any degenerate input.
+        assert_eq!(fit_width_percent_padded(30.0, 500.0, 1.0, 20.0, range), 100);
+    }
+
+    #[test]
+    fn fit_two_pages_uses_combined_width_and_gap() {
+        let letter = Some((612.0, 792.0));
+
+        // (1000 - 16) / (612 * 2) = 0.8039 -> 80%, narrower than 1000 / 792.
+        assert_eq!(fit_two_pages_percent(1000.0, 1000.0, letter, letter, 16.0, 1.0), 80);
+
+        // A short viewport binds on height: 600 / 792 = 0.7576 -> 76%.
+        assert_eq!(fit_two_pages_percent(2000.0, 600.0, letter, letter, 16.0, 1.0), 76);
+
+        // Retina halves the percent for the same physical fit.
+        assert_eq!(fit_two_pages_percent(2000.0, 1200.0, letter, letter, 16.0, 2.0), 76);
+    }
+
+    #[test]
+    fn fit_two_pages_handles_missing_cover_partner() {
+        let letter = Some((612.0, 792.0));
+
+        // Only one page: no gap, min(1000 / 612, 1000 / 792) = 1.2626 -> 126%.
+        assert_eq!(fit_two_pages_percent(1000.0, 1000.0, None, letter, 16.0, 1.0), 126);
+        assert_eq!(fit_two_pages_percent(1000.0, 1000.0, letter, None, 16.0, 1.0), 126);
+        assert_eq!(fit_two_pages_percent(1000.0, 1000.0, None, None, 16.0, 1.0), 100);
+    }
+
+    #[test]
+    fn fit_two_pages_uses_taller_page_and_clamps() {
+        let letter = Some((612.0, 792.0));
+        let tabloid = Some((792.0, 1224.0));
+
+        // Height: 1000 / 1224 = 0.817; width: 2000 / 1404 = 1.42 -> 82%.
+        assert_eq!(fit_two_pages_percent(2000.0, 1000.0, letter, tabloid, 0.0, 1.0), 82);
+
+        assert_eq!(fit_two_pages_percent(100_000.0, 100_000.0, letter, letter, 0.0, 1.0), 1600);
+    }
+
     #[test]
     fn visible_range_tracks_scroll_window() {
         let state = ViewportState {
@@ -404,6 +1021,139 @@ mod tests {
         assert_eq!(state.scroll_offset_px, 2200.0);
     }
 
+    #[test]
+    fn recenter_centers_a_page_smaller_than_the_viewport() {
+        let mut state = ViewportState {
+            mode: ViewMode::SinglePage,
+            viewport_width_px: 1280.0,
+            viewport_height_px: 800.0,
+            page_heights_px: vec![1000.0, 600.0],
+            page_spacing_px: 100.0,
+            scroll_offset_px: 1100.0,
+            ..ViewportState::default()
+        };
+
+        assert_eq!(recenter_on_page(&mut state, 400.0, 600.0), Some((440.0, 100.0)));
+        assert_eq!(state.scroll_offset_px, 1100.0);
+    }
+
+    #[test]
+    fn recenter_top_aligns_a_page_larger_than_the_viewport() {
+        let mut state = ViewportState {
+            mode: ViewMode::SinglePage,
+            viewport_width_px: 1280.0,
+            viewport_height_px: 800.0,
+            page_heights_px: vec![600.0, 1200.0],
+            page_spacing_px: 100.0,
+            scroll_offset_px: 1000.0,
+            ..ViewportState::default()
+        };
+
+        assert_eq!(recenter_on_page(&mut state, 1500.0, 1200.0), Some((0.0, 0.0)));
+        assert_eq!(state.scroll_offset_px, 700.0);
+    }
+
+    #[test]
+    fn recenter_is_a_no_op_in_continuous_mode() {
+        let mut state = ViewportState { scroll_offset_px: 250.0, ..ViewportState::default() };
+
+        assert_eq!(recenter_on_page(&mut state, 400.0, 600.0), None);
+        assert_eq!(state.scroll_offset_px, 250.0);
+    }
+
+    #[test]
+    fn with_viewport_seeds_size_dpr_and_fit_zoom() {
+        let state = ViewportState::with_viewport(1224.0, 1584.0, 2.0);
+
+        assert_eq!(state.viewport_width_px, 1224.0);
+        assert_eq!(state.viewport_height_px, 1584.0);
+        assert_eq!(state.dpr, 2.0);
+        assert_eq!(state.zoom_percent, 100);
+        assert_eq!(ViewportState::with_config(ViewportConfig::default()).zoom_percent, 101);
+
+        let default = ViewportState::default();
+        assert_eq!(default.zoom_percent, 100);
+        assert_eq!(default.page_heights_px, vec![1000.0]);
+    }
+
+    #[test]
+    fn fit_width_at_dpr_2_is_half_the_percent_of_dpr_1() {
+        let fit_width = |dpr: f32| {
+            let mut state = ViewportState {
+                zoom_mode: ZoomMode::FitWidth,
+                ..ViewportState::with_viewport(1224.0, 800.0, dpr)
+            };
+            state.on_viewport_resized(
+                state.viewport_width_px,
+                state.viewport_height_px,
+                DEFAULT_FIT_PAGE_SIZE_PX,
+            );
+            state.zoom_percent
+        };
+
+        assert_eq!(fit_width(1.0), 200);
+        assert_eq!(fit_width(2.0), 100);
+    }
+
+    #[test]
+    fn resize_in_fit_width_recomputes_zoom_and_keeps_center() {
+        let mut state = ViewportState {
+            zoom_mode: ZoomMode::FitWidth,
+            zoom_percent: 200,
+            viewport_width_px: 1000.0,
+            viewport_height_px: 800.0,
+            scroll_offset_px: 1600.0,
+            page_heights_px: vec![1600.0, 1600.0, 1600.0],
+            page_spacing_px: 0.0,
+            ..ViewportState::default()
+        };
+
+        state.on_viewport_resized(1500.0, 800.0, (500.0, 800.0));
+
+        assert_eq!(state.zoom_percent, 300);
+        assert_eq!(state.viewport_width_px, 1500.0);
+        assert_eq!(state.page_heights_px, vec![2400.0, 2400.0, 2400.0]);
+        // The center was 2000 / 4800 of the way down; it stays there.
+        assert_eq!(state.scroll_offset_px + 400.0, 2000.0 / 4800.0 * 7200.0);
+    }
+
+    #[test]
+    fn crop_height_replaces_page_height_in_layout() {
+        let mut state =
+            ViewportState { zoom_mode: ZoomMode::Percent, zoom_percent: 150, ..Default::default() };
+        state.page_spacing_px = 0.0;
+
+        state.set_page_heights(&[792.0, 792.0], Some(600.0));
+        assert_eq!(state.page_heights_px, vec![900.0, 900.0]);
+        assert_eq!(state.total_height_px(), 1800.0);
+
+        state.set_page_heights(&[792.0, 792.0], None);
+        assert_eq!(state.page_heights_px, vec![1188.0, 1188.0]);
+
+        // A crop taller than the page leaves the page at full height.
+        state.set_page_heights(&[792.0, 400.0], Some(600.0));
+        assert_eq!(state.page_heights_px, vec![900.0, 600.0]);
+    }
+
+    #[test]
+    fn resize_in_percent_mode_keeps_zoom() {
+        let mut state = ViewportState {
+            zoom_mode: ZoomMode::Percent,
+            zoom_percent: 150,
+            scroll_offset_px: 300.0,
+            page_heights_px: vec![1200.0, 1200.0],
+            ..ViewportState::default()
+        };
+
+        state.on_viewport_resized(640.0, 480.0, DEFAULT_FIT_PAGE_SIZE_PX);
+
+        assert_eq!(state.zoom_percent, 150);
+        assert_eq!(state.viewport_width_px, 640.0);
+        assert_eq!(state.viewport_height_px, 480.0);
+        assert_eq!(state.page_heights_px, vec![1200.0, 1200.0]);
+        assert_eq!(state.scroll_offset_px, 300.0);
+    }
+
     #[test]
     fn render_queue_prioritizes_visible_before_prefetch() {
         let mut queue = RenderQueue::new();
@@ -459,6 +1209,40 @@ mod tests {
         assert!(queue.is_empty());
     }
 
+    #[test]
+    fn render_queue_snapshot_matches_pop_order_without_consuming() {
+        let mut queue = RenderQueue::new();
+        queue.begin_generation();
+
+        let key = |page_index| RenderJobKey {
+            document_id: 3,
+            page_index,
+            zoom_percent: 100,
+            kind: RenderKind::Page,
+        };
+
+        queue.enqueue(key(7), RenderPriority::Prefetch);
+        queue.enqueue(key(2), RenderPriority::Thumbnail);
+        queue.enqueue(key(5), RenderPriority::Visible);
+        queue.enqueue(key(6), RenderPriority::Visible);
+        queue.enqueue(key(1), RenderPriority::Prefetch);
+
+        let snapshot = queue.snapshot();
+        assert_eq!(snapshot.len(), 5);
+        assert_eq!(queue.len(), 5);
+
+        let popped: Vec<(RenderJobKey, RenderPriority, u64)> =
+            std::iter::from_fn(|| queue.pop_next())
+                .map(|job| (job.key, job.priority, job.generation))
+                .collect();
+
+        assert_eq!(snapshot, popped);
+        assert_eq!(
+            snapshot.iter().map(|(key, _, _)| key.page_index).collect::<Vec<_>>(),
+            vec![5, 6, 2, 7, 1]
+        );
+    }
+
     #[test]
     fn lru_cache_evicts_oldest_entry() {
         let mut cache = LruCache::new(2);
@@ -487,6 +1271,36 @@ mod tests {
         assert!(cache.contains_key(&3));
     }
 
+    #[test]
+    fn scroll_animator_runs_from_start_to_target() {
+        let duration = Duration::from_millis(300);
+
+        for easing in [Easing::Linear, Easing::EaseInOut] {
+            let animator = ScrollAnimator::new(100.0, 900.0, duration, easing);
+            assert_eq!(animator.position(Duration::ZERO), 100.0);
+            assert_eq!(animator.position(duration), 900.0);
+            assert_eq!(animator.position(duration * 2), 900.0);
+            assert_eq!(animator.position(duration / 2), 500.0);
+        }
+
+        // A zero-length animation jumps straight to the target.
+        let instant = ScrollAnimator::new(0.0, 50.0, Duration::ZERO, Easing::EaseInOut);
+        assert_eq!(instant.position(Duration::ZERO), 50.0);
+    }
+
+    #[test]
+    fn ease_in_out_is_symmetric_around_midpoint() {
+        for step in 0..=10 {
+            let t = step as f32 / 10.0;
+            let sum = Easing::EaseInOut.apply(t) + Easing::EaseInOut.apply(1.0 - t);
+            assert!((sum - 1.0).abs() < 1e-6, "asymmetric at t={t}");
+        }
+
+        // Slower than linear at the start, faster through the middle.
+        assert!(Easing::EaseInOut.apply(0.1) < 0.1);
+        assert!(Easing::EaseInOut.apply(0.55) - Easing::EaseInOut.apply(0.45) > 0.1);
+    }
+
     #[test]
     fn prefetch_neighbors_are_symmetric_and_bounded() {
         let pages = prefetch_page_indices(5, 10, 2);
@@ -495,4 +1309,216 @@ mod tests {
         let edge = prefetch_page_indices(0, 3, 3);
         assert_eq!(edge, vec![1, 2]);
     }
+
+    #[test]
+    fn prefetch_radius_backs_off_under_memory_pressure() {
+        assert_eq!(effective_prefetch_radius(4, MemoryPressure::Normal), 4);
+        assert_eq!(effective_prefetch_radius(4, MemoryPressure::Warm), 2);
+        assert_eq!(effective_prefetch_radius(4, MemoryPressure::Hot), 0);
+        assert_eq!(effective_prefetch_radius(4, MemoryPressure::Critical), 0);
+
+        let radius = effective_prefetch_radius(3, MemoryPressure::Hot);
+        assert!(prefetch_page_indices(5, 10, radius).is_empty());
+    }
+
+    #[test]
+    fn clamp_scroll_offset_aligns_last_page_to_viewport_bottom() {
+        let max = clamp_scroll_offset(f32::MAX, 2000.0, 800.0, 1.0);
+        assert_eq!(max, 1200.0);
+        assert_eq!(max + 800.0, 2000.0);
+
+        assert_eq!(clamp_scroll_offset(-50.0, 2000.0, 800.0, 1.0), 0.0);
+        assert_eq!(clamp_scroll_offset(600.0, 2000.0, 800.0, 1.0), 600.0);
+    }
+
+    #[test]
+    fn clamp_scroll_offset_scales_document_height_by_zoom() {
+        assert_eq!(clamp_scroll_offset(10_000.0, 2000.0, 800.0, 2.0), 3200.0);
+        assert_eq!(clamp_scroll_offset(10_000.0, 2000.0, 800.0, 0.25), 0.0);
+    }
+
+    #[test]
+    fn clamp_scroll_offset_with_min_visible_allows_partial_overscroll() {
+        let offset = clamp_scroll_offset_with_min_visible(10_000.0, 2000.0, 800.0, 1.0, 200.0);
+        assert_eq!(offset, 1800.0);
+
+        let offset = clamp_scroll_offset_with_min_visible(10_000.0, 2000.0, 800.0, 1.0, 5000.0);
+        assert_eq!(offset, 1200.0);
+    }
+
+    #[test]
+    fn clamp_scroll_offset_rejects_non_finite_input() {
+        assert_eq!(clamp_scroll_offset(f32::NAN, 2000.0, 800.0, 1.0), 0.0);
+        assert_eq!(clamp_scroll_offset(500.0, 2000.0, 800.0, f32::NAN), 500.0);
+    }
+
+    #[test]
+    fn page_y_offsets_accumulate_heights_and_spacing() {
+        let mut offsets = PageYOffsets::new(16.0);
+        assert!(offsets.is_empty());
+
+        assert_eq!(offsets.push(1000.0), 0.0);
+        assert_eq!(offsets.push(500.0), 1016.0);
+        assert_eq!(offsets.push(800.0), 1532.0);
+
+        assert_eq!(offsets.offsets(), &[0.0, 1016.0, 1532.0]);
+        assert_eq!(offsets.total_height_px(), 2332.0);
+        assert_eq!(offsets.len(), 3);
+    }
+
+    fn run_kinetic(initial_velocity: f32, dt: f32) -> (f32, Vec<f32>) {
+        let mut scroller = KineticScroller::default();
+        scroller.on_fling(initial_velocity);
+
+        let mut distance = 0.0;
+        let mut velocities = vec![scroller.velocity];
+        while scroller.is_active() {
+            distance += scroller.tick(dt);
+            velocities.push(scroller.velocity);
+        }
+
+        (distance, velocities)
+    }
+
+    #[test]
+    fn kinetic_scroll_velocity_decays_monotonically_to_zero() {
+        let (_, velocities) = run_kinetic(2000.0, 1.0 / 60.0);
+
+        assert!(velocities.windows(2).all(|pair| pair[1] < pair[0]));
+        assert_eq!(velocities.last().copied(), Some(0.0));
+    }
+
+    #[test]
+    fn kinetic_scroll_distance_matches_closed_form() {
+        let initial_velocity = 2000.0;
+        let expected = initial_velocity / KineticScroller::DEFAULT_FRICTION;
+        let tolerance = KineticScroller::STOP_VELOCITY / KineticScroller::DEFAULT_FRICTION;
+
+        let (distance, _) = run_kinetic(initial_velocity, 1.0 / 60.0);
+        assert!((distance - expected).abs() <= tolerance, "distance {distance}");
+
+        let (reverse, _) = run_kinetic(-initial_velocity, 1.0 / 60.0);
+        assert!((reverse + expected).abs() <= tolerance, "distance {reverse}");
+    }
+
+    #[test]
+    fn kinetic_scroll_is_framerate_independent() {
+        let mut slow = KineticScroller::default();
+        let mut fast = KineticScroller::default();
+        slow.on_fling(1500.0);
+        fast.on_fling(1500.0);
+
+        let slow_distance: f32 = (0..30).map(|_| slow.tick(1.0 / 30.0)).sum();
+        let fast_distance: f32 = (0..120).map(|_| fast.tick(1.0 / 120.0)).sum();
+
+        assert!((slow_distance - fast_distance).abs() < 0.5);
+        assert!((slow.velocity - fast.velocity).abs() < 0.5);
+    }
+
+    #[test]
+    fn kinetic_scroll_ignores_idle_and_invalid_ticks() {
+        let mut scroller = KineticScroller::new(f32::NAN);
+        assert_eq!(scroller.friction(), KineticScroller::DEFAULT_FRICTION);
+        assert_eq!(scroller.tick(1.0 / 60.0), 0.0);
+
+        scroller.on_fling(1000.0);
+        assert_eq!(scroller.tick(0.0), 0.0);
+        assert_eq!(scroller.tick(-1.0), 0.0);
+        assert_eq!(scroller.velocity, 1000.0);
+    }
+
+    #[test]
+    fn kinetic_scroll_rejects_non_positive_friction() {
+        for friction in [0.0, -2.0, f32::INFINITY] {
+            let mut scroller = KineticScroller::new(friction);
+            assert_eq!(scroller.friction(), KineticScroller::DEFAULT_FRICTION);
+
+            scroller.set_friction(8.0);
+            scroller.set_friction(friction);
+            assert_eq!(scroller.friction(), KineticScroller::DEFAULT_FRICTION);
+
+            scroller.on_fling(1000.0);
+            assert!(scroller.tick(1.0 / 60.0).is_finite());
+        }
+    }
+
+    #[test]
+    fn visible_thumbnail_range_is_a_small_window_for_large_documents() {
+        let range = visible_thumbnail_range(0.0, 800.0, 180.0, 20.0, 1000, 2);
+        assert_eq!(range, 0..=5);
+
+        let range = visible_thumbnail_range(f32::MAX, 800.0, 180.0, 20.0, 1000, 2);
+        assert_eq!(range, 997..=999);
+    }
+
+    #[test]
+    fn visible_thumbnail_range_shifts_with_scroll() {
+        let range = visible_thumbnail_range(10_000.0, 800.0, 180.0, 20.0, 1000, 0);
+        assert_eq!(range, 50..=53);
+
+        let range = visible_thumbnail_range(10_200.0, 800.0, 180.0, 20.0, 1000, 1);
+        assert_eq!(range, 50..=55);
+    }
+
+    #[test]
+    fn visible_thumbnail_range_handles_degenerate_input() {
+        assert_eq!(visible_thumbnail_range(0.0, 800.0, 0.0, 0.0, 5, 2), 0..=4);
+        assert_eq!(visible_thumbnail_range(f32::NAN, 800.0, 180.0, 20.0, 10, 0), 0..=3);
+    }
+
+    #[test]
+    fn visible_thumbnail_range_is_empty_for_empty_document() {
+        let range = visible_thumbnail_range(0.0, 800.0, 180.0, 20.0, 0, 2);
+        assert!(range.is_empty());
+        assert_eq!(range.count(), 0);
+    }
+
+    #[test]
+    fn thumbnail_starting_at_viewport_bottom_is_not_visible() {
+        // Thumbnail 4 starts exactly at y = 800, the bottom edge of the viewport.
+        assert_eq!(visible_thumbnail_range(0.0, 800.0, 180.0, 20.0, 10, 0), 0..=3);
+        assert_eq!(visible_thumbnail_range(0.5, 800.0, 180.0, 20.0, 10, 0), 0..=4);
+    }
+
+    #[test]
+    fn render_queue_records_completions() {
+        let mut queue = RenderQueue::new();
+        let generation = queue.begin_generation();
+        let key = RenderJobKey {
+            document_id: 1,
+            page_index: 3,
+            zoom_percent: 100,
+            kind: RenderKind::Page,
+        };
+
+        queue.complete(key, Duration::from_millis(12));
+
+        let completions: Vec<_> = queue.recent_completions().copied().collect();
+        assert_eq!(
+            completions,
+            vec![RenderCompletion { key, generation, duration: Duration::from_millis(12) }]
+        );
+    }
+
+    #[test]
+    fn render_queue_completions_ring_buffer_is_capped() {
+        let mut queue = RenderQueue::new();
+        let total = RECENT_COMPLETIONS_CAPACITY as u32 + 5;
+
+        for page_index in 0..total {
+            let key = RenderJobKey {
+                document_id: 1,
+                page_index,
+                zoom_percent: 100,
+                kind: RenderKind::Thumbnail,
+            };
+            queue.complete(key, Duration::from_millis(1));
+        }
+
+        let pages: Vec<u32> =
+            queue.recent_completions().map(|completion| completion.key.page_index).collect();
+        assert_eq!(pages.len(), RECENT_COMPLETIONS_CAPACITY);
+        assert_eq!(pages.first().copied(), Some(5));
+        assert_eq!(pages.last().copied(), Some(total - 1));
+    }
 }
@@ -3,36 +3,152 @@ from __future__ import annotations
 
 from pathlib import Path
 
-
-def make_pdf(path: Path, pages: int) -> None:
-    objects: list[str | None] = []
-
-    def add(obj: str | None) -> int:
+# (title, zero-based page index, children)
+OutlineEntry = tuple[str, int, list["OutlineEntry"]]
+
+# Standard JPEG luminance DC Huffman codes, indexed by magnitude category.
+DC_CODES = [
+    "00", "010", "011", "100", "101", "110",
+    "1110", "11110", "111110", "1111110", "11111110", "111111110",
+]
+
+
+def make_solid_gray_jpeg(width: int, height: int, level: int) -> bytes:
+    """Baseline grayscale JPEG of a single gray level; dimensions must be multiples of 8."""
+    assert width % 8 == 0 and height % 8 == 0
+
+    def segment(marker: int, payload: bytes) -> bytes:
+        return bytes([0xFF, marker]) + (len(payload) + 2).to_bytes(2, "big") + payload
+
+    dc_counts = [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0]
+    # The AC table only needs end-of-block: every block is a flat DC value.
+    ac_counts = [1] + [0] * 15
+
+    header = b"\xff\xd8"
+    header += segment(0xDB, bytes([0x00]) + bytes([1] * 64))
+    header += segment(0xC0, bytes([8]) + height.to_bytes(2, "big") + width.to_bytes(2, "big")
+                      + bytes([1, 1, 0x11, 0]))
+    header += segment(0xC4, bytes([0x00] + dc_counts + list(range(12))))
+    header += segment(0xC4, bytes([0x10] + ac_counts + [0x00]))
+    header += segment(0xDA, bytes([1, 1, 0x00, 0, 63, 0]))
+
+    dc = 8 * (level - 128)
+    category = abs(dc).bit_length()
+    magnitude = dc if dc >= 0 else dc + (1 << category) - 1
+    first_block = DC_CODES[category] + (format(magnitude, f"0{category}b") if category else "")
+    blocks = (width // 8) * (height // 8)
+    bits = first_block + "0" + ("00" + "0") * (blocks - 1)
+    bits += "1" * (-len(bits) % 8)
+
+    scan = bytearray()
+    for i in range(0, len(bits), 8):
+        byte = int(bits[i : i + 8], 2)
+        scan.append(byte)
+        if byte == 0xFF:
+            scan.append(0x00)
+
+    return header + bytes(scan) + b"\xff\xd9"
+
+
+def make_pdf(
+    path: Path,
+    pages: int,
+    outline: list[OutlineEntry] | None = None,
+    omit_media_box: int | None = None,
+    jpeg: tuple[int, int, bytes] | None = None,
+    page_sizes: list[tuple[int, int]] | None = None,
+    with_text: bool = True,
+    full_page_image: bool = False,
+    image_filter: str = "DCTDecode",
+    pages_media_box: tuple[int, int] | None = None,
+) -> None:
+    objects: list[str | bytes | None] = []
+
+    def add(obj: str | bytes | None) -> int:
         objects.append(obj)
         return len(objects)
 
     font_id = add("<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>")
 
+    image_resources = ""
+    image_draw = ""
+    if jpeg:
+        image_width, image_height, data = jpeg
+        image_id = add(
+            (
+                "<< /Type /XObject /Subtype /Image "
+                f"/Width {image_width} /Height {image_height} "
+                f"/ColorSpace /DeviceGray /BitsPerComponent 8 /Filter /{image_filter} "
+                f"/Length {len(data)} >>\nstream\n"
+            ).encode("ascii")
+            + data
+            + b"\nendstream"
+        )
+        image_resources = f" /XObject << /Im1 {image_id} 0 R >>"
+        if full_page_image:
+            image_draw = " q 612 0 0 792 0 0 cm /Im1 Do Q"
+        else:
+            image_draw = f" q {image_width * 4} 0 0 {image_height * 4} 72 500 cm /Im1 Do Q"
+
     content_ids: list[int] = []
     page_ids: list[int] = []
 
     for i in range(pages):
-        text = f"BT /F1 24 Tf 72 720 Td (ButterPaper fixture page {i + 1}) Tj ET"
+        label = f"BT /F1 24 Tf 72 720 Td (ButterPaper fixture page {i + 1}) Tj ET" if with_text else ""
+        text = f"{label}{image_draw}".strip()
         stream = f"<< /Length {len(text.encode('utf-8'))} >>\nstream\n{text}\nendstream"
         content_ids.append(add(stream))
         page_ids.append(add(None))
 
     kids = " ".join(f"{pid} 0 R" for pid in page_ids)
-    pages_id = add(f"<< /Type /Pages /Kids [{kids}] /Count {pages} >>")
-    catalog_id = add(f"<< /Type /Catalog /Pages {pages_id} 0 R >>")
+    inherited_media_box = (
+        f" /MediaBox [0 0 {pages_media_box[0]} {pages_media_box[1]}]" if pages_media_box else ""
+    )
+    pages_id = add(f"<< /Type /Pages /Kids [{kids}] /Count {pages}{inherited_media_box} >>")
+
+    def add_outline_items(entries: list[OutlineEntry], parent_id: int) -> list[int]:
+        item_ids = [add(None) for _ in entries]
+
+        for index, (title, page_index, children) in enumerate(entries):
+            child_ids = add_outline_items(children, item_ids[index])
+            parts = [
+                f"/Title ({title})",
+                f"/Parent {parent_id} 0 R",
+                f"/Dest [{page_ids[page_index]} 0 R /Fit]",
+            ]
+            if index > 0:
+                parts.append(f"/Prev {item_ids[index - 1]} 0 R")
+            if index < len(entries) - 1:
+                parts.append(f"/Next {item_ids[index + 1]} 0 R")
+            if child_ids:
+                parts.append(f"/First {child_ids[0]} 0 R /Last {child_ids[-1]} 0 R")
+                parts.append(f"/Count {len(child_ids)}")
+            objects[item_ids[index] - 1] = "<< " + " ".join(parts) + " >>"
+
+        return item_ids
+
+    outlines_ref = ""
+    if outline:
+        outlines_id = add(None)
+        top_ids = add_outline_items(outline, outlines_id)
+        objects[outlines_id - 1] = (
+            f"<< /Type /Outlines /First {top_ids[0]} 0 R /Last {top_ids[-1]} 0 R "
+            f"/Count {len(top_ids)} >>"
+        )
+        outlines_ref = f" /Outlines {outlines_id} 0 R"
+
+    catalog_id = add(f"<< /Type /Catalog /Pages {pages_id} 0 R{outlines_ref} >>")
 
     for index, page_id in enumerate(page_ids):
+        width, height = page_sizes[index] if page_sizes else (612, 792)
+        omitted = index == omit_media_box or pages_media_box is not None
+        media_box = "" if omitted else f"/MediaBox [0 0 {width} {height}] "
         objects[page_id - 1] = (
             "<< /Type /Page "
             f"/Parent {pages_id} 0 R "
-            "/MediaBox [0 0 612 792] "
+            f"{media_box}"
             f"/Contents {content_ids[index]} 0 R "
-            f"/Resources << /Font << /F1 {font_id} 0 R >> >> >>"
+            f"/Resources << /Font << /F1 {font_id} 0 R >>{image_resources} >> >>"
         )
 
     assert all(obj is not None for obj in objects)
@@ -46,7 +162,7 @@ def make_pdf(path: Path, pages: int) -> None:
     for obj_number, obj in enumerate(objects, start=1):
         offsets.append(len(output))
         output.extend(f"{obj_number} 0 obj\n".encode("ascii"))
-        output.extend((obj or "").encode("utf-8"))
+        output.extend(obj if isinstance(obj, bytes) else (obj or "").encode("utf-8"))
         output.extend(b"\nendobj\n")
 
     xref_offset = len(output)
@@ -70,6 +186,43 @@ def main() -> None:
     make_pdf(root / "small.pdf", pages=1)
     make_pdf(root / "medium.pdf", pages=5)
     make_pdf(root / "large.pdf", pages=20)
+    make_pdf(
+        root / "outline.pdf",
+        pages=4,
+        outline=[
+            (
+                "Chapter 1",
+                0,
+                [
+                    ("Section 1.1", 1, []),
+                    ("Section 1.2", 2, [("Details", 2, [])]),
+                ],
+            ),
+            ("Chapter 2", 3, []),
+        ],
+    )
+
+    make_pdf(root / "missing-mediabox.pdf", pages=2, omit_media_box=1)
+    make_pdf(root / "inherited-mediabox.pdf", pages=2, pages_media_box=(792, 1224))
+    make_pdf(root / "image.pdf", pages=1, jpeg=(16, 8, make_solid_gray_jpeg(16, 8, 200)))
+    make_pdf(
+        root / "scanned.pdf",
+        pages=1,
+        jpeg=(16, 8, make_solid_gray_jpeg(16, 8, 200)),
+        with_text=False,
+        full_page_image=True,
+    )
+    make_pdf(
+        root / "unsupported-image.pdf",
+        pages=1,
+        jpeg=(16, 8, make_solid_gray_jpeg(16, 8, 200)),
+        image_filter="JBIG2Decode",
+    )
+    make_pdf(
+        root / "mixed-sizes.pdf",
+        pages=3,
+        page_sizes=[(612, 792), (792, 1224), (612, 792)],
+    )
 
     (root / "invalid.pdf").write_text("this is not a pdf\n", encoding="utf-8")
     (root / "encrypted-marker.pdf").write_bytes(b"%PDF-1.4\n1 0 obj\n<< /Encrypt true >>\nendobj\n%%EOF\n")